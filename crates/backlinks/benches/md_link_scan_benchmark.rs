@@ -0,0 +1,34 @@
+use backlinks::md_link_matches;
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+fn build_line(link_count: usize) -> String {
+    let mut line = String::from("Some prose before the first link. ");
+    for index in 0..link_count {
+        line.push_str(&format!("[note {index}](Note {index}.md) "));
+    }
+    line
+}
+
+fn md_link_scan(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("md_link_scan");
+
+    for link_count in [0, 1, 8] {
+        let line = build_line(link_count);
+        group.throughput(Throughput::Bytes(line.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("literal", link_count),
+            &line,
+            |bench, line| bench.iter(|| black_box(md_link_matches(line, true))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("regex", link_count),
+            &line,
+            |bench, line| bench.iter(|| black_box(md_link_matches(line, false))),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, md_link_scan);
+criterion_main!(benches);
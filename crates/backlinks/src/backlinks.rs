@@ -0,0 +1,2370 @@
+//! Backlinks: find every markdown link in a project that points back at a
+//! given note, similar to the "linked mentions" panel in note-taking tools.
+
+mod backlinks_export;
+mod backlinks_hover;
+mod backlinks_panel;
+mod backlinks_search;
+mod backlinks_settings;
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
+
+use collections::{HashMap, HashSet};
+use db::kvp::KeyValueStore;
+use fs::{Fs, MTime};
+use futures::{StreamExt as _, future, stream};
+use gpui::{App, AppContext as _, Entity, Task};
+use project::{Project, ProjectItem as _, ProjectPath};
+use regex::Regex;
+use util::ResultExt as _;
+
+pub use backlinks_panel::BacklinksPanel;
+pub use backlinks_settings::{
+    AmbiguousStemPolicy, BacklinkClickBehavior, BacklinkScanScope, BacklinksDensity,
+    BacklinksSettings, BacklinksSortOrder, LinkNormalizationMode, LinkPathFormat, LinkSyntax,
+    NoteIdentity,
+};
+
+pub fn init(cx: &mut App) {
+    BacklinksSettings::register(cx);
+    backlinks_panel::init(cx);
+    backlinks_hover::init(cx);
+    backlinks_export::init(cx);
+    backlinks_search::init(cx);
+}
+
+/// Matches standard markdown links: `[text](target)`.
+pub static MD_LINK_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\(([^)]+)\)").unwrap());
+
+/// Whether a scan can use [`find_md_links_literal`] instead of
+/// [`MD_LINK_PATTERN`] for the common case: no custom link patterns
+/// (aliases) and no non-default stem normalization. Headings and block-id
+/// anchors are unaffected either way, since `split_anchor` parses them out
+/// of the extracted target text after a match is found, regardless of how
+/// it was found.
+pub fn can_use_literal_md_link_scan(
+    custom_link_regexes: &[(ProjectPath, Regex)],
+    link_normalization: LinkNormalizationMode,
+) -> bool {
+    custom_link_regexes.is_empty() && link_normalization == LinkNormalizationMode::Strict
+}
+
+/// Matches markdown links (`[text](target)`) in `line`, returning
+/// `(whole_match_range, link_target_range)` pairs in the same order
+/// [`MD_LINK_PATTERN`] would. Uses a literal byte scan when `use_literal` is
+/// set, falling back to the regex otherwise; see
+/// [`can_use_literal_md_link_scan`].
+pub fn md_link_matches(line: &str, use_literal: bool) -> Vec<(Range<usize>, Range<usize>)> {
+    if use_literal {
+        find_md_links_literal(line)
+    } else {
+        MD_LINK_PATTERN
+            .captures_iter(line)
+            .filter_map(|capture| {
+                let whole_match = capture.get(0)?;
+                let link_target = capture.get(2)?;
+                Some((whole_match.range(), link_target.range()))
+            })
+            .collect()
+    }
+}
+
+/// Finds the same matches as [`MD_LINK_PATTERN`] via a literal byte scan
+/// instead of a regex: compiling and running two regexes per line (this one
+/// and [`TASK_ITEM_PATTERN`]) across a large vault is the scan's hot path,
+/// and the common case — no aliases or normalization — doesn't need the
+/// regex's generality. Markdown's link delimiters (`[`, `]`, `(`, `)`) are
+/// all single-byte ASCII, so scanning by byte offset never lands inside a
+/// multi-byte UTF-8 sequence.
+pub fn find_md_links_literal(line: &str) -> Vec<(Range<usize>, Range<usize>)> {
+    let bytes = line.as_bytes();
+    let mut matches = Vec::new();
+    let mut search_from = 0;
+    while search_from < bytes.len() {
+        let Some(open_bracket) = find_byte(bytes, search_from, b'[') else {
+            break;
+        };
+        let Some(close_bracket) = find_byte(bytes, open_bracket + 1, b']') else {
+            break;
+        };
+        if bytes.get(close_bracket + 1) != Some(&b'(') {
+            search_from = open_bracket + 1;
+            continue;
+        }
+        let open_paren = close_bracket + 1;
+        let Some(close_paren) = find_byte(bytes, open_paren + 1, b')') else {
+            search_from = open_bracket + 1;
+            continue;
+        };
+        // The regex's target group is `[^)]+`: at least one character.
+        if close_paren == open_paren + 1 {
+            search_from = open_bracket + 1;
+            continue;
+        }
+        matches.push((open_bracket..close_paren + 1, open_paren + 1..close_paren));
+        search_from = close_paren + 1;
+    }
+    matches
+}
+
+/// The index of the first occurrence of `needle` in `bytes` at or after
+/// `from`, if any.
+fn find_byte(bytes: &[u8], from: usize, needle: u8) -> Option<usize> {
+    bytes[from..].iter().position(|&byte| byte == needle).map(|offset| from + offset)
+}
+
+/// What a backlinks scan resolves against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BacklinkTarget {
+    /// Links to this specific note.
+    File(ProjectPath),
+    /// Links to any markdown note contained in this folder (recursively).
+    /// [`BacklinkEntry::target`] records which one a given link actually
+    /// resolved to, so results can be grouped by it.
+    Folder(ProjectPath),
+}
+
+/// A single markdown link that resolves to some other note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacklinkEntry {
+    pub source: ProjectPath,
+    pub abs_path: PathBuf,
+    /// The note this link resolves to. Equal to the scan target in
+    /// [`BacklinkTarget::File`] mode; varies per-entry in
+    /// [`BacklinkTarget::Folder`] mode.
+    pub target: ProjectPath,
+    pub line: u32,
+    pub context: String,
+    /// Byte ranges within `context` of each link that matched the target, so
+    /// the UI can emphasize them instead of showing the whole line muted.
+    pub match_ranges: Vec<Range<usize>>,
+    /// The untruncated version of `context`, i.e. the trimmed line before
+    /// `BacklinksSettings::max_context_length` clipped it. Equal to `context`
+    /// when it wasn't clipped. Lets the panel show the full line on demand
+    /// without re-reading the source file.
+    pub full_context: String,
+    /// `match_ranges`, but as byte ranges within `full_context`.
+    pub full_match_ranges: Vec<Range<usize>>,
+    /// Up to `BacklinksSettings::context_lines` lines immediately before
+    /// `context`, trimmed the same way and bounded by the start of the file.
+    /// Empty when `context_lines` is `0` or `BacklinksSettings::show_context`
+    /// is disabled.
+    pub context_before: Vec<String>,
+    /// The lines immediately after `context`, symmetric to `context_before`
+    /// and bounded by the end of the file.
+    pub context_after: Vec<String>,
+    /// Whether this link was found inside the file's YAML frontmatter block,
+    /// e.g. a `related: [Note](Note.md)` relationship, rather than the body.
+    pub in_frontmatter: bool,
+    /// Whether this entry matched one of
+    /// `BacklinksSettings::custom_link_patterns` rather than a standard
+    /// markdown link.
+    pub is_custom: bool,
+    /// Whether this entry was scanned from `source`'s open, unsaved buffer
+    /// rather than the file on disk, since `source` has edits that haven't
+    /// been saved yet.
+    pub from_unsaved_buffer: bool,
+    /// The heading or block anchor the link targets, e.g. the `#Heading` in
+    /// `[text](Note.md#Heading)`, if any.
+    pub anchor: Option<LinkAnchor>,
+    /// Whether `anchor` actually exists in `target`, e.g. the heading hasn't
+    /// been renamed or removed. `None` when the link has no anchor.
+    pub anchor_valid: Option<bool>,
+    /// Whether this link's target stem also matches another note in the
+    /// project, and `BacklinksSettings::ambiguous_stem_matching` couldn't
+    /// resolve which one it most plausibly targets, so it's counted against
+    /// `target` but flagged for the UI to call out.
+    pub is_ambiguous: bool,
+    /// The checkbox state of the line this link was found on, if it's a
+    /// markdown task list item (e.g. `- [ ] follow up on [Note](Note.md)`), so the
+    /// panel can surface actionable references separately from prose
+    /// mentions. `None` for links on ordinary lines.
+    pub task_state: Option<TaskState>,
+}
+
+/// The checkbox state of a markdown task list item, e.g. `- [ ]` or `- [x]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    Open,
+    Done,
+}
+
+/// Matches a markdown task list item's bullet and checkbox, regardless of
+/// indentation or bullet marker (`-`, `*`, `+`): `  - [ ] text` or
+/// `* [x] text`.
+static TASK_ITEM_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*[-*+]\s+\[([ xX])\](?:\s|$)").unwrap());
+
+/// Parses `line`'s leading bullet and checkbox, if it's a markdown task list
+/// item, returning its checkbox state. `None` for lines that aren't task
+/// items at all.
+fn parse_task_state(line: &str) -> Option<TaskState> {
+    let capture = TASK_ITEM_PATTERN.captures(line)?;
+    let checkbox = capture.get(1)?.as_str();
+    Some(if checkbox == " " {
+        TaskState::Open
+    } else {
+        TaskState::Done
+    })
+}
+
+/// A heading or block-id anchor within a markdown link target, e.g. the
+/// `#Heading` in `[text](Note.md#Heading)` or the `#^block-id` in
+/// `[text](Note.md#^block-id)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkAnchor {
+    Heading(String),
+    Block(String),
+}
+
+/// Splits `link_target` into its path and, if present, its heading or block
+/// anchor (the part after the first `#`). A `#^block-id` anchor is a block
+/// reference; anything else after the `#` is treated as a heading.
+fn split_anchor(link_target: &str) -> (&str, Option<LinkAnchor>) {
+    let Some((path, anchor)) = link_target.split_once('#') else {
+        return (link_target, None);
+    };
+    let anchor = match anchor.strip_prefix('^') {
+        Some(block_id) => LinkAnchor::Block(block_id.to_string()),
+        None => LinkAnchor::Heading(anchor.to_string()),
+    };
+    (path, Some(anchor))
+}
+
+/// Parses the markdown headings and block ids (`^block-id` at the end of a
+/// line) appearing in `contents`, so a link's anchor can be checked against
+/// them. Heading text is trimmed of its leading `#`s and surrounding
+/// whitespace; block ids are matched without their leading `^`.
+fn headings_and_block_ids(contents: &str) -> (HashSet<String>, HashSet<String>) {
+    let mut headings = HashSet::new();
+    let mut block_ids = HashSet::new();
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim();
+            if !heading.is_empty() {
+                headings.insert(heading.to_string());
+            }
+        }
+        if let Some((_, block_id)) = trimmed.rsplit_once(" ^") {
+            if !block_id.is_empty() {
+                block_ids.insert(block_id.to_string());
+            }
+        }
+    }
+    (headings, block_ids)
+}
+
+/// The result of a backlinks scan: the entries to show, plus how many matches
+/// were found in total before `BacklinksSettings::max_entries` was applied.
+#[derive(Debug, Default)]
+pub struct BacklinkResults {
+    pub entries: Vec<BacklinkEntry>,
+    pub total_matches: usize,
+}
+
+impl BacklinkResults {
+    pub fn hidden_count(&self) -> usize {
+        self.total_matches.saturating_sub(self.entries.len())
+    }
+}
+
+/// Schemes treated as pointing outside the vault, so a link like
+/// `[x](https://example.com/Note.md)` isn't mistaken for a reference to a
+/// same-named note. `file` is deliberately not in this list: see
+/// [`vault_link_target`].
+const EXTERNAL_URL_SCHEMES: &[&str] = &["http", "https", "mailto", "ftp", "ftps", "tel", "data"];
+
+/// Resolves `link_target` to the path it should be matched against vault
+/// notes, or `None` if it's an external URL that shouldn't be treated as a
+/// backlink at all. A `file:` URL has its scheme stripped and the remaining
+/// path matched like any other link, since it may still point back into the
+/// vault; every other scheme in [`EXTERNAL_URL_SCHEMES`] is assumed external.
+/// A target with no scheme (the common case) passes through unchanged.
+fn vault_link_target(link_target: &str) -> Option<&str> {
+    let Some((scheme, rest)) = link_target.split_once(':') else {
+        return Some(link_target);
+    };
+    if scheme.eq_ignore_ascii_case("file") {
+        return Some(rest.trim_start_matches('/'));
+    }
+    if EXTERNAL_URL_SCHEMES
+        .iter()
+        .any(|external_scheme| scheme.eq_ignore_ascii_case(external_scheme))
+    {
+        return None;
+    }
+    Some(link_target)
+}
+
+/// Extracts the file stem a markdown link target resolves to, percent-decoding
+/// it first so links like `[x](My%20Note.md)` match a file named
+/// `My Note.md`. Falls back to the raw target if it isn't validly encoded.
+fn link_target_stem(link_target: &str) -> String {
+    let decoded = urlencoding::decode(link_target)
+        .map(std::borrow::Cow::into_owned)
+        .unwrap_or_else(|_| link_target.to_string());
+    decoded
+        .rsplit('/')
+        .next()
+        .unwrap_or(&decoded)
+        .trim_end_matches(".md")
+        .to_string()
+}
+
+/// Lowercases `stem` and collapses hyphens, underscores, and runs of
+/// whitespace into single spaces, so e.g. `my-note` and `My  Note` both
+/// normalize to `my note`.
+fn normalize_stem(stem: &str) -> String {
+    stem.to_lowercase()
+        .replace(['-', '_'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns whether `link_stem` (from a link target) refers to `candidate_stem`
+/// (a file's stem), according to `mode`.
+fn stems_match(link_stem: &str, candidate_stem: &str, mode: LinkNormalizationMode) -> bool {
+    match mode {
+        LinkNormalizationMode::Strict => link_stem == candidate_stem,
+        LinkNormalizationMode::Normalized => {
+            normalize_stem(link_stem) == normalize_stem(candidate_stem)
+        }
+    }
+}
+
+/// The key under which a note's stem is grouped to find every other note
+/// that a link to it could be confused with, consistent with how `mode`
+/// compares a link's stem against a candidate's.
+fn stem_group_key(stem: &str, mode: LinkNormalizationMode) -> String {
+    match mode {
+        LinkNormalizationMode::Strict => stem.to_string(),
+        LinkNormalizationMode::Normalized => normalize_stem(stem),
+    }
+}
+
+/// Resolves which of `candidates` (all notes sharing a link's target stem,
+/// including the note the link is actually being matched against) the link
+/// most plausibly points to, given the link's full target path and the note
+/// containing it. Prefers a candidate an explicit path fragment in the link
+/// narrows it down to, then a candidate in the same folder as `source`.
+/// Returns `None` when neither heuristic narrows it down to exactly one
+/// candidate, i.e. the link is truly ambiguous between several of them.
+fn resolve_ambiguous_stem<'a>(
+    link_path: &str,
+    source: &ProjectPath,
+    candidates: &'a [ProjectPath],
+) -> Option<&'a ProjectPath> {
+    if let Some(link_folder) = Path::new(link_path)
+        .parent()
+        .filter(|folder| *folder != Path::new(""))
+    {
+        let explicit_matches: Vec<_> = candidates
+            .iter()
+            .filter(|candidate| {
+                candidate
+                    .path
+                    .as_std_path()
+                    .parent()
+                    .is_some_and(|folder| folder.ends_with(link_folder))
+            })
+            .collect();
+        match explicit_matches.as_slice() {
+            [only] => return Some(only),
+            [] => {}
+            _ => return None,
+        }
+    }
+
+    let source_folder = source.path.as_std_path().parent();
+    let same_folder_matches: Vec<_> = candidates
+        .iter()
+        .filter(|candidate| candidate.path.as_std_path().parent() == source_folder)
+        .collect();
+    match same_folder_matches.as_slice() {
+        [only] => Some(only),
+        _ => None,
+    }
+}
+
+/// Resolves a markdown link's target path against the directory containing
+/// `source_abs_path`, normalizing `.` and `..` components lexically (without
+/// touching the filesystem), so a link like `../../shared/Note.md` resolves
+/// to an absolute path even when it climbs above `source_abs_path`'s
+/// worktree into a sibling one. Returns `None` for a link that isn't a plain
+/// relative path, e.g. a URL or an absolute path, since those don't resolve
+/// relative to `source_abs_path` at all.
+fn resolve_relative_link_path(link_path: &str, source_abs_path: &Path) -> Option<PathBuf> {
+    if link_path.contains("://") || Path::new(link_path).is_absolute() {
+        return None;
+    }
+    let mut resolved = source_abs_path.parent()?.to_path_buf();
+    for component in Path::new(link_path).components() {
+        match component {
+            std::path::Component::ParentDir => {
+                if !resolved.pop() {
+                    return None;
+                }
+            }
+            std::path::Component::CurDir => {}
+            std::path::Component::Normal(segment) => resolved.push(segment),
+            std::path::Component::RootDir | std::path::Component::Prefix(_) => return None,
+        }
+    }
+    Some(resolved)
+}
+
+/// Trims `line` for display in a backlink's context preview: its leading
+/// indentation is kept when `preserve_leading_indentation` is set (only the
+/// trailing whitespace is stripped), otherwise both ends are trimmed.
+fn trim_context_line(line: &str, preserve_leading_indentation: bool) -> String {
+    if preserve_leading_indentation { line.trim_end() } else { line.trim() }.to_string()
+}
+
+/// Truncates `context` to at most `max_length` characters, keeping a window
+/// centered on the first of `match_ranges` and replacing clipped ends with an
+/// ellipsis, so a long line doesn't force the panel wider than it needs to
+/// be. `match_ranges` (byte ranges into `context`) are rewritten in place to
+/// stay valid for the returned string. A no-op, returning `context`
+/// unchanged, when it already fits or `max_length` is `0` (unlimited).
+fn truncate_context_centered(
+    context: &str,
+    match_ranges: &mut [Range<usize>],
+    max_length: usize,
+) -> String {
+    let chars: Vec<(usize, char)> = context.char_indices().collect();
+    if max_length == 0 || chars.len() <= max_length {
+        return context.to_string();
+    }
+    const ELLIPSIS: char = '…';
+
+    let byte_to_char_index = |byte_offset: usize| {
+        chars
+            .iter()
+            .position(|(index, _)| *index >= byte_offset)
+            .unwrap_or(chars.len())
+    };
+
+    let first_match_char = match_ranges
+        .first()
+        .map_or(0, |range| byte_to_char_index(range.start));
+    let half_window = max_length / 2;
+    let window_end = (first_match_char.saturating_sub(half_window) + max_length).min(chars.len());
+    let window_start = window_end.saturating_sub(max_length);
+    let show_leading_ellipsis = window_start > 0;
+    let show_trailing_ellipsis = window_end < chars.len();
+
+    let mut truncated_chars = Vec::with_capacity(max_length + 2);
+    if show_leading_ellipsis {
+        truncated_chars.push(ELLIPSIS);
+    }
+    truncated_chars.extend(chars[window_start..window_end].iter().map(|(_, c)| *c));
+    if show_trailing_ellipsis {
+        truncated_chars.push(ELLIPSIS);
+    }
+
+    let mut byte_offsets = Vec::with_capacity(truncated_chars.len() + 1);
+    let mut byte_offset = 0;
+    for truncated_char in &truncated_chars {
+        byte_offsets.push(byte_offset);
+        byte_offset += truncated_char.len_utf8();
+    }
+    byte_offsets.push(byte_offset);
+
+    let leading_ellipsis_chars = usize::from(show_leading_ellipsis);
+    for match_range in match_ranges.iter_mut() {
+        let start_char = byte_to_char_index(match_range.start).clamp(window_start, window_end);
+        let end_char = byte_to_char_index(match_range.end).clamp(window_start, window_end);
+        let start_index = start_char - window_start + leading_ellipsis_chars;
+        let end_index = end_char - window_start + leading_ellipsis_chars;
+        *match_range = byte_offsets[start_index]..byte_offsets[end_index];
+    }
+
+    truncated_chars.into_iter().collect()
+}
+
+/// Compiles `templates` (each containing a literal `{name}` placeholder) into
+/// one regex per `target`, substituting `{name}` with the target's escaped
+/// file stem. A template is validated once (against a placeholder stem)
+/// before being expanded per-target, so a single invalid entry in settings is
+/// logged and skipped without affecting the others.
+fn compile_custom_link_patterns(
+    templates: &[String],
+    targets: &[(ProjectPath, String)],
+) -> Vec<(ProjectPath, Regex)> {
+    let mut compiled = Vec::new();
+    for template in templates {
+        if let Err(error) = Regex::new(&template.replace("{name}", "probe")) {
+            log::warn!(
+                "backlinks: ignoring invalid custom_link_patterns entry {template:?}: {error}"
+            );
+            continue;
+        }
+        for (target, stem) in targets {
+            let pattern = template.replace("{name}", &regex::escape(stem));
+            if let Ok(regex) = Regex::new(&pattern) {
+                compiled.push((target.clone(), regex));
+            }
+        }
+    }
+    compiled
+}
+
+/// Records a single matched link/mention on the current line, merging it into
+/// the last entry in `line_entries` if that entry already covers the same
+/// target and match kind (custom vs. standard), so multiple matches to the
+/// same note on one line share a single [`BacklinkEntry`].
+#[allow(clippy::too_many_arguments)]
+fn push_backlink_match(
+    line_entries: &mut Vec<BacklinkEntry>,
+    source: &ProjectPath,
+    abs_path: &std::path::Path,
+    matched_target: &ProjectPath,
+    line: u32,
+    context: &str,
+    match_range: Range<usize>,
+    in_frontmatter: bool,
+    is_custom: bool,
+    from_unsaved_buffer: bool,
+    anchor: Option<LinkAnchor>,
+    is_ambiguous: bool,
+    task_state: Option<TaskState>,
+) {
+    if let Some(last_entry) = line_entries.last_mut().filter(|entry: &&mut BacklinkEntry| {
+        entry.target == *matched_target && entry.is_custom == is_custom
+    }) {
+        last_entry.match_ranges.push(match_range);
+        last_entry.is_ambiguous |= is_ambiguous;
+    } else {
+        line_entries.push(BacklinkEntry {
+            source: source.clone(),
+            abs_path: abs_path.to_path_buf(),
+            target: matched_target.clone(),
+            line,
+            context: context.to_string(),
+            match_ranges: vec![match_range],
+            full_context: String::new(),
+            full_match_ranges: Vec::new(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            in_frontmatter,
+            is_custom,
+            from_unsaved_buffer,
+            anchor,
+            anchor_valid: None,
+            is_ambiguous,
+            task_state,
+        });
+    }
+}
+
+/// How similar two file stems are, used by [`relevance_score`]: the length
+/// of their common prefix (case-insensitively), as a fraction of the longer
+/// stem's length. `1.0` for identical stems, `0.0` when they share no
+/// leading characters.
+fn name_similarity(a: &str, b: &str) -> f32 {
+    let a = a.to_lowercase();
+    let b = b.to_lowercase();
+    let shared_prefix_len = a.chars().zip(b.chars()).take_while(|(x, y)| x == y).count();
+    let longest_len = a.chars().count().max(b.chars().count());
+    if longest_len == 0 {
+        return 1.0;
+    }
+    shared_prefix_len as f32 / longest_len as f32
+}
+
+/// A relevance score for [`BacklinksSortOrder::Relevance`]: sources that
+/// share more of `target`'s folder path, then sources whose file stem is
+/// more similar to `target`'s, are the most relevant.
+fn relevance_score(source: &ProjectPath, target: &ProjectPath) -> (usize, f32) {
+    let shared_folder_depth = source
+        .path
+        .parent()
+        .into_iter()
+        .flat_map(|dir| dir.components())
+        .zip(target.path.parent().into_iter().flat_map(|dir| dir.components()))
+        .take_while(|(source_component, target_component)| source_component == target_component)
+        .count();
+    let name_similarity = match (source.path.file_stem(), target.path.file_stem()) {
+        (Some(source_stem), Some(target_stem)) => name_similarity(source_stem, target_stem),
+        _ => 0.0,
+    };
+    (shared_folder_depth, name_similarity)
+}
+
+/// Namespace for the per-source open counters backing
+/// [`BacklinksSortOrder::Frequency`], persisted across restarts so the
+/// panel's ordering keeps reflecting actual navigation habits.
+const OPEN_COUNT_NAMESPACE: &str = "backlinks_open_counts";
+
+fn open_count_key(abs_path: &Path) -> String {
+    abs_path.display().to_string()
+}
+
+fn open_count(store: &KeyValueStore, abs_path: &Path) -> usize {
+    store
+        .scoped(OPEN_COUNT_NAMESPACE)
+        .read(&open_count_key(abs_path))
+        .log_err()
+        .flatten()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Records that the backlink source at `abs_path` was opened from the
+/// panel, for [`BacklinksSortOrder::Frequency`] to float it higher next
+/// time entries are sorted.
+pub(crate) fn record_backlink_opened(abs_path: &Path, cx: &App) {
+    let store = KeyValueStore::global(cx);
+    let key = open_count_key(abs_path);
+    let count = open_count(&store, abs_path) + 1;
+    cx.background_spawn(async move {
+        store.scoped(OPEN_COUNT_NAMESPACE).write(key, count.to_string()).await
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Returns the 0-indexed line range of the YAML frontmatter block at the top
+/// of `contents` (the lines between the opening and closing `---` fences), if
+/// the file starts with one.
+fn frontmatter_line_range(contents: &str) -> Option<Range<usize>> {
+    let mut lines = contents.lines().enumerate();
+    let (_, first_line) = lines.next()?;
+    if first_line.trim_end() != "---" {
+        return None;
+    }
+    for (line_index, line) in lines {
+        if line.trim_end() == "---" {
+            return Some(0..line_index + 1);
+        }
+    }
+    None
+}
+
+/// Whether `path` should be scanned as a markdown note. Case-insensitive
+/// since case-preserving-but-insensitive filesystems (e.g. default macOS and
+/// Windows setups) happily hand back `.MD` or `.Md` files alongside `.md`
+/// ones.
+pub(crate) fn is_markdown_extension(path: &util::rel_path::RelPath) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("md"))
+}
+
+/// Caches a note's `title`/`id` frontmatter field (see [`note_identity_field`])
+/// against the mtime it was read at, so a vault-wide scan only re-reads and
+/// re-parses a note's frontmatter after it's actually changed on disk.
+static NOTE_IDENTITY_CACHE: LazyLock<
+    RwLock<HashMap<(ProjectPath, &'static str), (MTime, Option<String>)>>,
+> = LazyLock::new(|| RwLock::new(HashMap::default()));
+
+/// Parses `field: value` out of `contents`' YAML frontmatter block (see
+/// [`frontmatter_line_range`]), trimming the value and a single layer of
+/// surrounding quotes. Only a plain `field: value` line is recognized, not
+/// YAML's other scalar or block forms, which is enough for the flat
+/// `id:`/`title:` frontmatter most note-taking tools write.
+fn parse_frontmatter_field(contents: &str, field: &str) -> Option<String> {
+    let frontmatter_range = frontmatter_line_range(contents)?;
+    let prefix = format!("{field}:");
+    for line in contents.lines().take(frontmatter_range.end).skip(1) {
+        let Some(value) = line.trim_start().strip_prefix(&prefix) else {
+            continue;
+        };
+        let value = value.trim();
+        let unquoted = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|value| value.strip_suffix('\'')));
+        let value = unquoted.unwrap_or(value);
+        if !value.is_empty() {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// Returns `note`'s `field` (`"title"` or `"id"`) frontmatter value, or
+/// `None` if it has no frontmatter or that field. Checked against
+/// [`NOTE_IDENTITY_CACHE`] first, keyed by `abs_path`'s mtime; a dirty buffer
+/// bypasses the cache entirely, since its on-disk mtime doesn't reflect its
+/// unsaved content.
+async fn note_identity_field(
+    fs: &dyn Fs,
+    note: &ProjectPath,
+    abs_path: &Path,
+    field: &'static str,
+    dirty_contents: Option<String>,
+) -> Option<String> {
+    if let Some(contents) = dirty_contents {
+        return parse_frontmatter_field(&contents, field);
+    }
+    let mtime = fs.metadata(abs_path).await.ok().flatten()?.mtime;
+    let cache_key = (note.clone(), field);
+    let cached = NOTE_IDENTITY_CACHE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&cache_key)
+        .filter(|(cached_mtime, _)| *cached_mtime == mtime)
+        .map(|(_, value)| value.clone());
+    if let Some(cached) = cached {
+        return cached;
+    }
+    let contents = fs.load(abs_path).await.ok()?;
+    let value = parse_frontmatter_field(&contents, field);
+    NOTE_IDENTITY_CACHE
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(cache_key, (mtime, value.clone()));
+    value
+}
+
+/// Resolves each of `notes_with_fallback`'s match string per `note_identity`:
+/// under [`NoteIdentity::Filename`] this is a no-op, returning the filename
+/// stems unchanged. Under [`NoteIdentity::Title`] or [`NoteIdentity::Id`],
+/// each note's `title`/`id` frontmatter field is used instead (concurrently,
+/// up to a fixed cap, and cached — see [`note_identity_field`]), falling
+/// back to the filename stem for a note that doesn't have the field.
+async fn resolve_note_identities(
+    fs: &Arc<dyn Fs>,
+    worktree_roots: &[(project::WorktreeId, Arc<Path>)],
+    notes_with_fallback: Vec<(ProjectPath, String)>,
+    note_identity: NoteIdentity,
+    dirty_buffer_contents: &HashMap<ProjectPath, String>,
+) -> Vec<(ProjectPath, String)> {
+    let field = match note_identity {
+        NoteIdentity::Filename => return notes_with_fallback,
+        NoteIdentity::Title => "title",
+        NoteIdentity::Id => "id",
+    };
+    const MAX_CONCURRENT_FRONTMATTER_READS: usize = 16;
+    stream::iter(notes_with_fallback)
+        .map(|(note, fallback_stem)| {
+            let fs = fs.clone();
+            let worktree_root = worktree_roots
+                .iter()
+                .find(|(id, _)| *id == note.worktree_id)
+                .map(|(_, root)| root.clone());
+            let dirty_contents = dirty_buffer_contents.get(&note).cloned();
+            async move {
+                let Some(worktree_root) = worktree_root else {
+                    return (note, fallback_stem);
+                };
+                let abs_path = worktree_root.join(note.path.as_std_path());
+                let identity =
+                    note_identity_field(fs.as_ref(), &note, &abs_path, field, dirty_contents)
+                        .await
+                        .unwrap_or(fallback_stem);
+                (note, identity)
+            }
+        })
+        .buffer_unordered(MAX_CONCURRENT_FRONTMATTER_READS)
+        .collect()
+        .await
+}
+
+/// The folder that [`BacklinkScanScope::Folder`] and
+/// [`BacklinkScanScope::FolderRecursive`] restrict candidate source files to:
+/// the note's own parent directory for a single-note target, or the folder
+/// itself for [`BacklinkTarget::Folder`], since that's already the folder
+/// being scanned.
+fn scan_root_folder(target: &BacklinkTarget) -> ProjectPath {
+    match target {
+        BacklinkTarget::File(note) => ProjectPath {
+            worktree_id: note.worktree_id,
+            path: note
+                .path
+                .parent()
+                .map(|parent| parent.into_arc())
+                .unwrap_or_else(util::rel_path::RelPath::empty_arc),
+        },
+        BacklinkTarget::Folder(folder) => folder.clone(),
+    }
+}
+
+/// Finds every link in the project that resolves to `target`, capping the
+/// returned entries at `BacklinksSettings::max_entries` only after sorting,
+/// so the cap doesn't drop entries ahead of more relevant ones. Unless
+/// `BacklinksSettings::include_self_references` is set, the scan's target
+/// notes are not scanned as sources, so a target note's links back to itself
+/// are excluded (and, for a [`BacklinkTarget::Folder`], so are links between
+/// two notes inside that folder). Links inside a file's
+/// YAML frontmatter block (e.g. `related: [Note](Note.md)`) are detected the
+/// same as any other link, with `BacklinkEntry::in_frontmatter` set so the UI
+/// can call out structured relationships.
+///
+/// When `target` is a [`BacklinkTarget::Folder`], links to any markdown note
+/// contained in that folder are matched and aggregated, sorted by which note
+/// each link resolves to ([`BacklinkEntry::target`]) so the UI can group them.
+///
+/// When a link's target stem matches more than one note in the project (e.g.
+/// `Notes/Index.md` and `Archive/Index.md` both matching a link to "Index"),
+/// `BacklinksSettings::ambiguous_stem_matching` decides which one the link is
+/// counted against: under [`AmbiguousStemPolicy::Heuristic`], an explicit
+/// path fragment in the link or a shared folder with its source note is used
+/// to narrow it down, falling back to counting it against every stem match
+/// it resolves against in this scan with [`BacklinkEntry::is_ambiguous`] set.
+/// A link whose relative path names a folder (e.g. `../../shared/Note.md`) is
+/// resolved exactly first (normalizing `..` against its source file's
+/// directory, even past its worktree's root into a sibling one); a bare file
+/// name, or a path that isn't a plain relative path, falls back to the stem
+/// heuristics above.
+///
+/// When `BacklinksSettings::show_context` is disabled, [`BacklinkEntry::context`]
+/// and [`BacklinkEntry::match_ranges`] are left empty instead of being built
+/// and truncated, which is the expensive part of a scan over a vault with
+/// many large files. `BacklinkEntry::line` is always populated, so the panel
+/// can still open an entry at the right line.
+///
+/// Candidate files are read and scanned concurrently (see
+/// [`scan_file_for_backlinks`]), up to a fixed cap, rather than one at a
+/// time. The list of candidate files is collected in full before scanning
+/// starts, and the task resolves only once every candidate has been scanned.
+///
+/// A worktree with `BacklinksSettings::exclude_from_scanning` set in its
+/// local settings is skipped entirely, both as a source of candidate files
+/// and as a target: a link pointing into it is left unmatched rather than
+/// reported, the same as a link to a note that doesn't exist.
+///
+/// When `BacklinksSettings::note_identity` isn't [`NoteIdentity::Filename`],
+/// the string a link must match is each note's `title`/`id` YAML frontmatter
+/// field instead of its filename stem, falling back to the filename stem for
+/// notes without that field. See [`resolve_note_identities`].
+pub fn find_backlinks(
+    project: Entity<Project>,
+    target: BacklinkTarget,
+    cx: &mut App,
+) -> Task<BacklinkResults> {
+    let settings = BacklinksSettings::get_global(cx);
+    let max_entries = settings.max_entries;
+    let include_self_references = settings.include_self_references;
+    let link_normalization = settings.link_normalization;
+    let max_context_length = settings.max_context_length;
+    let preserve_leading_indentation = settings.preserve_leading_indentation;
+    let show_context = settings.show_context;
+    let context_lines = settings.context_lines;
+    let custom_link_patterns = settings.custom_link_patterns.clone();
+    let sort_order = settings.sort_order;
+    let ambiguous_stem_matching = settings.ambiguous_stem_matching;
+    let note_identity = settings.note_identity;
+    let open_tasks_only = settings.open_tasks_only;
+    let scan_scope = settings.scan_scope;
+    let fs = project.read(cx).fs().clone();
+    let open_count_store = KeyValueStore::global(cx);
+
+    // The active note's folder, to restrict candidate source files to under
+    // `BacklinkScanScope::Folder`/`FolderRecursive`. `None` under
+    // `BacklinkScanScope::Vault`, where every markdown file is a candidate.
+    let scan_scope_folder = match scan_scope {
+        BacklinkScanScope::Vault => None,
+        BacklinkScanScope::Folder | BacklinkScanScope::FolderRecursive => {
+            Some(scan_root_folder(&target))
+        }
+    };
+
+    let scanned_worktrees: Vec<_> = project
+        .read(cx)
+        .visible_worktrees(cx)
+        .filter(|worktree| !BacklinksSettings::is_worktree_excluded(worktree.read(cx).id(), cx))
+        .collect();
+    let worktree_roots: Vec<_> = scanned_worktrees
+        .iter()
+        .map(|worktree| (worktree.read(cx).id(), worktree.read(cx).abs_path()))
+        .collect();
+    let all_markdown_files: Vec<_> = scanned_worktrees
+        .iter()
+        .flat_map(|worktree| {
+            let worktree_id = worktree.read(cx).id();
+            worktree
+                .read(cx)
+                .files(false, 0)
+                .filter(|entry| is_markdown_extension(&entry.path))
+                .map(|entry| ProjectPath {
+                    worktree_id,
+                    path: entry.path.clone(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|note| match &scan_scope_folder {
+            None => true,
+            Some(folder) => match scan_scope {
+                BacklinkScanScope::FolderRecursive => note.starts_with(folder),
+                _ => {
+                    note.worktree_id == folder.worktree_id
+                        && note.path.parent() == Some(folder.path.as_ref())
+                }
+            },
+        })
+        .collect();
+
+    // The notes this scan is looking for links to, each paired with the file
+    // stem a link target must match absent frontmatter-based identity (see
+    // `resolve_note_identities` below).
+    let target_stems: Vec<(ProjectPath, String)> = match &target {
+        BacklinkTarget::File(note) => {
+            let Some(stem) = note.path.file_stem().map(|stem| stem.to_string()) else {
+                return Task::ready(BacklinkResults::default());
+            };
+            vec![(note.clone(), stem)]
+        }
+        BacklinkTarget::Folder(folder) => all_markdown_files
+            .iter()
+            .filter(|note| note.starts_with(folder))
+            .filter_map(|note| {
+                note.path
+                    .file_stem()
+                    .map(|stem| ((*note).clone(), stem.to_string()))
+            })
+            .collect(),
+    };
+    if target_stems.is_empty() {
+        return Task::ready(BacklinkResults::default());
+    }
+
+    // Every note's filename stem, the fallback `resolve_note_identities` used
+    // for `stem_groups` below when it lacks the configured frontmatter field.
+    let all_note_stems: Vec<(ProjectPath, String)> = all_markdown_files
+        .iter()
+        .filter_map(|note| {
+            note.path
+                .file_stem()
+                .map(|stem| (note.clone(), stem.to_string()))
+        })
+        .collect();
+
+    // Each target's absolute filesystem path, so a link using a relative
+    // path (e.g. `../../shared/Note.md`) can be resolved and compared
+    // exactly, even when it climbs into a different worktree than its
+    // source.
+    let target_abs_paths: HashMap<ProjectPath, PathBuf> = target_stems
+        .iter()
+        .filter_map(|(target, _)| {
+            let (_, worktree_root) =
+                worktree_roots.iter().find(|(id, _)| *id == target.worktree_id)?;
+            Some((target.clone(), worktree_root.join(target.path.as_std_path())))
+        })
+        .collect();
+
+    // Unsaved edits aren't on disk yet, so a scan that only reads via
+    // `fs.load` would miss them (or find stale matches) until the buffer is
+    // saved. Snapshot dirty buffers' in-memory text up front and prefer it
+    // over disk for their source file.
+    let dirty_buffer_contents: HashMap<ProjectPath, String> = project
+        .read(cx)
+        .opened_buffers(cx)
+        .into_iter()
+        .filter_map(|buffer| {
+            let buffer = buffer.read(cx);
+            if !buffer.is_dirty() {
+                return None;
+            }
+            let project_path = buffer.project_path(cx)?;
+            Some((project_path, buffer.text()))
+        })
+        .collect();
+
+    let worktree_roots = Arc::new(worktree_roots);
+    let target_abs_paths = Arc::new(target_abs_paths);
+    let dirty_buffer_contents = Arc::new(dirty_buffer_contents);
+
+    cx.background_spawn(async move {
+        let targets = resolve_note_identities(
+            &fs,
+            &worktree_roots,
+            target_stems,
+            note_identity,
+            &dirty_buffer_contents,
+        )
+        .await;
+
+        // Every note sharing an identity string with another, grouped by
+        // that shared string, so a link matching one of them can be checked
+        // for ambiguity against the others. Built eagerly only under
+        // `AmbiguousStemPolicy::Heuristic`, since `AmbiguousStemPolicy::MatchAll`
+        // never needs it.
+        let stem_groups: HashMap<String, Vec<ProjectPath>> = if ambiguous_stem_matching
+            == AmbiguousStemPolicy::Heuristic
+        {
+            let all_note_identities = resolve_note_identities(
+                &fs,
+                &worktree_roots,
+                all_note_stems,
+                note_identity,
+                &dirty_buffer_contents,
+            )
+            .await;
+            let mut groups: HashMap<String, Vec<ProjectPath>> = HashMap::default();
+            for (note, stem) in &all_note_identities {
+                groups
+                    .entry(stem_group_key(stem, link_normalization))
+                    .or_default()
+                    .push(note.clone());
+            }
+            groups.retain(|_, notes| notes.len() > 1);
+            groups
+        } else {
+            HashMap::default()
+        };
+
+        let custom_link_regexes = compile_custom_link_patterns(&custom_link_patterns, &targets);
+
+        let targets = Arc::new(targets);
+        let stem_groups = Arc::new(stem_groups);
+        let custom_link_regexes = Arc::new(custom_link_regexes);
+
+        // Caps how many files are read and scanned at once, so a very large
+        // vault doesn't flood the filesystem with concurrent reads.
+        const MAX_CONCURRENT_FILE_SCANS: usize = 16;
+        let entries_by_file: Vec<Vec<BacklinkEntry>> = stream::iter(
+            all_markdown_files.into_iter().filter(|note| {
+                include_self_references || !targets.iter().any(|(target, _)| target == note)
+            }),
+        )
+        .map(|source| {
+            let worktree_roots = worktree_roots.clone();
+            let targets = targets.clone();
+            let target_abs_paths = target_abs_paths.clone();
+            let stem_groups = stem_groups.clone();
+            let custom_link_regexes = custom_link_regexes.clone();
+            let dirty_buffer_contents = dirty_buffer_contents.clone();
+            let fs = fs.clone();
+            async move {
+                scan_file_for_backlinks(
+                    source,
+                    &worktree_roots,
+                    &targets,
+                    &target_abs_paths,
+                    &stem_groups,
+                    &custom_link_regexes,
+                    &dirty_buffer_contents,
+                    fs.as_ref(),
+                    link_normalization,
+                    preserve_leading_indentation,
+                    show_context,
+                    max_context_length,
+                    context_lines,
+                )
+                .await
+            }
+        })
+        .buffer_unordered(MAX_CONCURRENT_FILE_SCANS)
+        .collect()
+        .await;
+        let mut entries: Vec<BacklinkEntry> = entries_by_file.into_iter().flatten().collect();
+        if open_tasks_only {
+            entries.retain(|entry| entry.task_state == Some(TaskState::Open));
+        }
+
+        entries.sort_by(|a, b| {
+            let target_order = a.target.path.cmp(&b.target.path);
+            match sort_order {
+                BacklinksSortOrder::Location => target_order
+                    .then(a.source.path.cmp(&b.source.path))
+                    .then(a.line.cmp(&b.line)),
+                BacklinksSortOrder::Relevance => {
+                    let (a_folder_depth, a_name_similarity) = relevance_score(&a.source, &a.target);
+                    let (b_folder_depth, b_name_similarity) = relevance_score(&b.source, &b.target);
+                    target_order
+                        .then(b_folder_depth.cmp(&a_folder_depth))
+                        .then(b_name_similarity.total_cmp(&a_name_similarity))
+                        .then(a.source.path.cmp(&b.source.path))
+                        .then(a.line.cmp(&b.line))
+                }
+                BacklinksSortOrder::Frequency => {
+                    let a_count = open_count(&open_count_store, &a.abs_path);
+                    let b_count = open_count(&open_count_store, &b.abs_path);
+                    target_order
+                        .then(b_count.cmp(&a_count))
+                        .then(a.source.path.cmp(&b.source.path))
+                        .then(a.line.cmp(&b.line))
+                }
+            }
+        });
+        let total_matches = entries.len();
+        entries.truncate(max_entries);
+
+        // Anchors are resolved against the target note's headings/block ids
+        // after truncation, so this never reads more target notes than the
+        // entries actually being shown.
+        let mut target_anchors: HashMap<ProjectPath, (HashSet<String>, HashSet<String>)> =
+            HashMap::default();
+        for target in entries
+            .iter()
+            .filter(|entry| entry.anchor.is_some())
+            .map(|entry| entry.target.clone())
+            .collect::<HashSet<_>>()
+        {
+            let Some((_, worktree_root)) =
+                worktree_roots.iter().find(|(id, _)| *id == target.worktree_id)
+            else {
+                continue;
+            };
+            let abs_path = worktree_root.join(target.path.as_std_path());
+            let contents = if let Some(buffer_contents) = dirty_buffer_contents.get(&target) {
+                buffer_contents.clone()
+            } else if let Ok(contents) = fs.load(&abs_path).await {
+                contents
+            } else {
+                continue;
+            };
+            target_anchors.insert(target, headings_and_block_ids(&contents));
+        }
+        for entry in &mut entries {
+            let Some(anchor) = &entry.anchor else {
+                continue;
+            };
+            let Some((headings, block_ids)) = target_anchors.get(&entry.target) else {
+                continue;
+            };
+            entry.anchor_valid = Some(match anchor {
+                LinkAnchor::Heading(heading) => headings.contains(heading),
+                LinkAnchor::Block(block_id) => block_ids.contains(block_id),
+            });
+        }
+
+        BacklinkResults {
+            entries,
+            total_matches,
+        }
+    })
+}
+
+/// One note's reference to another within a [`scan_vault_backlink_graph`]
+/// export: the note the link was found in, and the line it appears on.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BacklinkGraphReference {
+    pub source: PathBuf,
+    pub line: u32,
+}
+
+/// One note and every other note that links to it, within a full-vault
+/// [`scan_vault_backlink_graph`] export.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BacklinkGraphNode {
+    pub target: PathBuf,
+    pub references: Vec<BacklinkGraphReference>,
+}
+
+/// Builds the full backlink graph for every markdown note in `project`: each
+/// note mapped to every other note (and line number) that links to it.
+/// Unlike [`find_backlinks`], this isn't scoped to a single note or folder
+/// and isn't capped by `BacklinksSettings::max_entries`, since the point is a
+/// complete dataset for analysis or backup rather than something meant for
+/// on-screen display. Reuses [`scan_file_for_backlinks`] — the same per-file
+/// matcher `find_backlinks` uses — run concurrently across the vault off the
+/// main thread, so building the graph doesn't block the UI even for a large
+/// vault.
+///
+/// Returns the total number of notes the scan will cover alongside the task,
+/// so a caller can show it before the task has produced any progress of its
+/// own. `scanned_files` is incremented once per note as its scan completes,
+/// for that caller to poll. If `cancelled` is set before the scan finishes,
+/// no further notes are submitted to be scanned (ones already in flight
+/// still run to completion) and the task resolves to `None` instead of a
+/// finished graph.
+///
+/// Like [`find_backlinks`], a worktree with
+/// `BacklinksSettings::exclude_from_scanning` set is left out of the graph
+/// entirely, and nodes are matched by `BacklinksSettings::note_identity`
+/// rather than always by filename.
+pub fn scan_vault_backlink_graph(
+    project: Entity<Project>,
+    scanned_files: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+    cx: &mut App,
+) -> (usize, Task<Option<Vec<BacklinkGraphNode>>>) {
+    let settings = BacklinksSettings::get_global(cx);
+    let include_self_references = settings.include_self_references;
+    let link_normalization = settings.link_normalization;
+    let preserve_leading_indentation = settings.preserve_leading_indentation;
+    let custom_link_patterns = settings.custom_link_patterns.clone();
+    let ambiguous_stem_matching = settings.ambiguous_stem_matching;
+    let note_identity = settings.note_identity;
+    let fs = project.read(cx).fs().clone();
+
+    let scanned_worktrees: Vec<_> = project
+        .read(cx)
+        .visible_worktrees(cx)
+        .filter(|worktree| !BacklinksSettings::is_worktree_excluded(worktree.read(cx).id(), cx))
+        .collect();
+    let worktree_roots: Vec<_> = scanned_worktrees
+        .iter()
+        .map(|worktree| (worktree.read(cx).id(), worktree.read(cx).abs_path()))
+        .collect();
+    let all_markdown_files: Vec<_> = scanned_worktrees
+        .iter()
+        .flat_map(|worktree| {
+            let worktree_id = worktree.read(cx).id();
+            worktree
+                .read(cx)
+                .files(false, 0)
+                .filter(|entry| is_markdown_extension(&entry.path))
+                .map(|entry| ProjectPath {
+                    worktree_id,
+                    path: entry.path.clone(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let total_files = all_markdown_files.len();
+
+    let all_note_stems: Vec<(ProjectPath, String)> = all_markdown_files
+        .iter()
+        .filter_map(|note| {
+            note.path
+                .file_stem()
+                .map(|stem| (note.clone(), stem.to_string()))
+        })
+        .collect();
+    if all_note_stems.is_empty() {
+        return (0, Task::ready(Some(Vec::new())));
+    }
+
+    let target_abs_paths: HashMap<ProjectPath, PathBuf> = all_note_stems
+        .iter()
+        .filter_map(|(target, _)| {
+            let (_, worktree_root) =
+                worktree_roots.iter().find(|(id, _)| *id == target.worktree_id)?;
+            Some((target.clone(), worktree_root.join(target.path.as_std_path())))
+        })
+        .collect();
+
+    let dirty_buffer_contents: HashMap<ProjectPath, String> = project
+        .read(cx)
+        .opened_buffers(cx)
+        .into_iter()
+        .filter_map(|buffer| {
+            let buffer = buffer.read(cx);
+            if !buffer.is_dirty() {
+                return None;
+            }
+            let project_path = buffer.project_path(cx)?;
+            Some((project_path, buffer.text()))
+        })
+        .collect();
+
+    let worktree_roots = Arc::new(worktree_roots);
+    let target_abs_paths = Arc::new(target_abs_paths);
+    let dirty_buffer_contents = Arc::new(dirty_buffer_contents);
+
+    let task = cx.background_spawn(async move {
+        let targets = resolve_note_identities(
+            &fs,
+            &worktree_roots,
+            all_note_stems,
+            note_identity,
+            &dirty_buffer_contents,
+        )
+        .await;
+
+        let stem_groups: HashMap<String, Vec<ProjectPath>> = if ambiguous_stem_matching
+            == AmbiguousStemPolicy::Heuristic
+        {
+            let mut groups: HashMap<String, Vec<ProjectPath>> = HashMap::default();
+            for (note, stem) in &targets {
+                groups
+                    .entry(stem_group_key(stem, link_normalization))
+                    .or_default()
+                    .push(note.clone());
+            }
+            groups.retain(|_, notes| notes.len() > 1);
+            groups
+        } else {
+            HashMap::default()
+        };
+
+        let custom_link_regexes = compile_custom_link_patterns(&custom_link_patterns, &targets);
+
+        let targets = Arc::new(targets);
+        let stem_groups = Arc::new(stem_groups);
+        let custom_link_regexes = Arc::new(custom_link_regexes);
+
+        const MAX_CONCURRENT_FILE_SCANS: usize = 16;
+        let scan_cancelled = cancelled.clone();
+        // Every note is a target here, so self-references are filtered per
+        // link below rather than by dropping target notes as sources.
+        let entries_by_file: Vec<Vec<BacklinkEntry>> = stream::iter(all_markdown_files)
+        .take_while(move |_| future::ready(!scan_cancelled.load(Ordering::Relaxed)))
+        .map(|source| {
+            let worktree_roots = worktree_roots.clone();
+            let targets = targets.clone();
+            let target_abs_paths = target_abs_paths.clone();
+            let stem_groups = stem_groups.clone();
+            let custom_link_regexes = custom_link_regexes.clone();
+            let dirty_buffer_contents = dirty_buffer_contents.clone();
+            let fs = fs.clone();
+            let scanned_files = scanned_files.clone();
+            async move {
+                let entries = scan_file_for_backlinks(
+                    source,
+                    &worktree_roots,
+                    &targets,
+                    &target_abs_paths,
+                    &stem_groups,
+                    &custom_link_regexes,
+                    &dirty_buffer_contents,
+                    fs.as_ref(),
+                    link_normalization,
+                    preserve_leading_indentation,
+                    false,
+                    0,
+                    0,
+                )
+                .await;
+                scanned_files.fetch_add(1, Ordering::Relaxed);
+                entries
+            }
+        })
+        .buffer_unordered(MAX_CONCURRENT_FILE_SCANS)
+        .collect()
+        .await;
+
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let mut references_by_target: HashMap<ProjectPath, Vec<BacklinkGraphReference>> =
+            HashMap::default();
+        for entry in entries_by_file
+            .into_iter()
+            .flatten()
+            .filter(|entry| include_self_references || entry.source != entry.target)
+        {
+            references_by_target
+                .entry(entry.target)
+                .or_default()
+                .push(BacklinkGraphReference {
+                    source: entry.abs_path,
+                    line: entry.line,
+                });
+        }
+
+        let mut nodes: Vec<BacklinkGraphNode> = references_by_target
+            .into_iter()
+            .map(|(target, mut references)| {
+                references.sort_by(|a, b| a.source.cmp(&b.source).then(a.line.cmp(&b.line)));
+                let target = target_abs_paths
+                    .get(&target)
+                    .cloned()
+                    .unwrap_or_else(|| target.path.as_std_path().to_path_buf());
+                BacklinkGraphNode { target, references }
+            })
+            .collect();
+        nodes.sort_by(|a, b| a.target.cmp(&b.target));
+        Some(nodes)
+    });
+
+    (total_files, task)
+}
+
+/// Scans a single file for links matching `targets`, for use by
+/// [`find_backlinks`] as the per-file unit of work in its concurrent scan.
+/// Returns an empty `Vec` if `source`'s worktree can't be found or its
+/// contents can't be read, rather than failing the whole scan.
+async fn scan_file_for_backlinks(
+    source: ProjectPath,
+    worktree_roots: &[(project::WorktreeId, Arc<Path>)],
+    targets: &[(ProjectPath, String)],
+    target_abs_paths: &HashMap<ProjectPath, PathBuf>,
+    stem_groups: &HashMap<String, Vec<ProjectPath>>,
+    custom_link_regexes: &[(ProjectPath, Regex)],
+    dirty_buffer_contents: &HashMap<ProjectPath, String>,
+    fs: &dyn Fs,
+    link_normalization: LinkNormalizationMode,
+    preserve_leading_indentation: bool,
+    show_context: bool,
+    max_context_length: usize,
+    context_lines: usize,
+) -> Vec<BacklinkEntry> {
+    let mut entries = Vec::new();
+    let Some((_, worktree_root)) = worktree_roots
+        .iter()
+        .find(|(id, _)| *id == source.worktree_id)
+    else {
+        return entries;
+    };
+    let abs_path = worktree_root.join(source.path.as_std_path());
+    let from_unsaved_buffer = dirty_buffer_contents.contains_key(&source);
+    let contents = if let Some(buffer_contents) = dirty_buffer_contents.get(&source) {
+        buffer_contents.clone()
+    } else {
+        let Ok(contents) = fs.load(&abs_path).await else {
+            return entries;
+        };
+        contents
+    };
+    let frontmatter_range = frontmatter_line_range(&contents);
+    let all_lines: Vec<&str> = contents.lines().collect();
+    for (line_index, line) in all_lines.iter().copied().enumerate() {
+        let context_offset = if preserve_leading_indentation {
+            0
+        } else {
+            line.len() - line.trim_start().len()
+        };
+        // Only materialize the trimmed preview text when it's going to be
+        // shown; building and later truncating it is the expensive part of a
+        // scan over a very large vault.
+        let context = if show_context {
+            trim_context_line(line, preserve_leading_indentation)
+        } else {
+            String::new()
+        };
+        let (context_before, context_after) = if show_context && context_lines > 0 {
+            let before_start = line_index.saturating_sub(context_lines);
+            let after_end = (line_index + 1 + context_lines).min(all_lines.len());
+            (
+                all_lines[before_start..line_index]
+                    .iter()
+                    .map(|line| trim_context_line(line, preserve_leading_indentation))
+                    .collect(),
+                all_lines[line_index + 1..after_end]
+                    .iter()
+                    .map(|line| trim_context_line(line, preserve_leading_indentation))
+                    .collect(),
+            )
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        let in_frontmatter = frontmatter_range
+            .as_ref()
+            .is_some_and(|range| range.contains(&line_index));
+        let task_state = parse_task_state(line);
+        let mut line_entries: Vec<BacklinkEntry> = Vec::new();
+        let use_literal_scan =
+            can_use_literal_md_link_scan(custom_link_regexes, link_normalization);
+        for (whole_match, link_target) in md_link_matches(line, use_literal_scan) {
+            let (link_path, anchor) = split_anchor(&line[link_target]);
+            let Some(link_path) = vault_link_target(link_path) else {
+                continue;
+            };
+            let link_stem = link_target_stem(link_path);
+            let Some((matched_target, _)) = targets
+                .iter()
+                .find(|(_, stem)| stems_match(&link_stem, stem, link_normalization))
+            else {
+                continue;
+            };
+            // A link whose relative path names a folder resolves to an exact
+            // location, even across worktree boundaries, so it takes priority
+            // over the stem-based heuristics below: a resolution that lands
+            // somewhere other than `matched_target` means this link isn't
+            // really pointing at it, whatever its stem looks like. A bare file
+            // name (e.g. `My%20Note.md`) is matched by stem instead.
+            let resolved_link_path = if link_path.contains('/') {
+                resolve_relative_link_path(link_path, &abs_path)
+            } else {
+                None
+            };
+            let is_exact_path_match = resolved_link_path.as_deref()
+                == target_abs_paths.get(matched_target).map(PathBuf::as_path);
+            if let (Some(resolved_link_path), Some(target_abs_path)) =
+                (&resolved_link_path, target_abs_paths.get(matched_target))
+                && resolved_link_path != target_abs_path
+            {
+                continue;
+            }
+            let is_ambiguous = if is_exact_path_match {
+                false
+            } else {
+                match stem_groups.get(&stem_group_key(&link_stem, link_normalization)) {
+                    Some(candidates) => {
+                        match resolve_ambiguous_stem(link_path, &source, candidates) {
+                            Some(resolved) if resolved != matched_target => continue,
+                            Some(_resolved) => false,
+                            None => true,
+                        }
+                    }
+                    None => false,
+                }
+            };
+            let match_range = whole_match.start.saturating_sub(context_offset)
+                ..whole_match.end.saturating_sub(context_offset);
+            push_backlink_match(
+                &mut line_entries,
+                &source,
+                &abs_path,
+                matched_target,
+                line_index as u32,
+                &context,
+                match_range,
+                in_frontmatter,
+                false,
+                from_unsaved_buffer,
+                anchor,
+                is_ambiguous,
+                task_state,
+            );
+        }
+        for (custom_target, custom_regex) in custom_link_regexes {
+            for custom_match in custom_regex.find_iter(line) {
+                let match_range = custom_match.start().saturating_sub(context_offset)
+                    ..custom_match.end().saturating_sub(context_offset);
+                push_backlink_match(
+                    &mut line_entries,
+                    &source,
+                    &abs_path,
+                    custom_target,
+                    line_index as u32,
+                    &context,
+                    match_range,
+                    in_frontmatter,
+                    true,
+                    from_unsaved_buffer,
+                    None,
+                    false,
+                    task_state,
+                );
+            }
+        }
+        for mut entry in line_entries {
+            if show_context {
+                entry.full_context = entry.context.clone();
+                entry.full_match_ranges = entry.match_ranges.clone();
+                entry.context = truncate_context_centered(
+                    &entry.context,
+                    &mut entry.match_ranges,
+                    max_context_length,
+                );
+                entry.context_before = context_before.clone();
+                entry.context_after = context_after.clone();
+            } else {
+                entry.match_ranges.clear();
+            }
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+/// A link in the active note that doesn't resolve to any known file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    pub line: u32,
+    pub link_text: String,
+    pub target: String,
+}
+
+/// The markdown file stems known to `project`, keyed the same way
+/// [`scan_text_for_broken_links`] keys link targets so the two can be
+/// compared directly. Shared by [`find_broken_links`] and the backlinks
+/// panel's live, as-you-type rescan of the active note's outgoing links.
+pub(crate) fn known_markdown_stems(
+    project: &Project,
+    link_normalization: LinkNormalizationMode,
+    cx: &App,
+) -> HashSet<String> {
+    project
+        .visible_worktrees(cx)
+        .flat_map(|worktree| {
+            worktree
+                .read(cx)
+                .files(false, 0)
+                .filter(|entry| is_markdown_extension(&entry.path))
+                .filter_map(|entry| entry.path.file_stem().map(|stem| stem.to_string()))
+                .collect::<Vec<_>>()
+        })
+        .map(|stem| match link_normalization {
+            LinkNormalizationMode::Strict => stem,
+            LinkNormalizationMode::Normalized => normalize_stem(&stem),
+        })
+        .collect()
+}
+
+/// The core of [`find_broken_links`], factored out so the backlinks panel can
+/// rescan a single already-loaded buffer's text live as the user types,
+/// without going through `fs` at all.
+pub(crate) fn scan_text_for_broken_links(
+    contents: &str,
+    known_stems: &HashSet<String>,
+    link_normalization: LinkNormalizationMode,
+) -> Vec<BrokenLink> {
+    let mut broken_links = Vec::new();
+    for (line_index, line) in contents.lines().enumerate() {
+        for capture in MD_LINK_PATTERN.captures_iter(line) {
+            let Some(link_target) = capture.get(2) else {
+                continue;
+            };
+            let Some(vault_target) = vault_link_target(link_target.as_str()) else {
+                continue;
+            };
+            let link_stem = link_target_stem(vault_target);
+            let link_stem = match link_normalization {
+                LinkNormalizationMode::Strict => link_stem,
+                LinkNormalizationMode::Normalized => normalize_stem(&link_stem),
+            };
+            if known_stems.contains(&link_stem) {
+                continue;
+            }
+            broken_links.push(BrokenLink {
+                line: line_index as u32,
+                link_text: capture
+                    .get(1)
+                    .map_or(String::new(), |link_text| link_text.as_str().to_string()),
+                target: link_target.as_str().to_string(),
+            });
+        }
+    }
+    broken_links
+}
+
+/// Parses `note`'s outgoing markdown links and returns the ones whose target
+/// doesn't match any markdown file stem in the project, so typos and deleted
+/// targets can be spotted and fixed.
+pub fn find_broken_links(
+    project: Entity<Project>,
+    note: ProjectPath,
+    cx: &mut App,
+) -> Task<Vec<BrokenLink>> {
+    let link_normalization = BacklinksSettings::get_global(cx).link_normalization;
+    let fs = project.read(cx).fs().clone();
+    let Some(worktree) = project.read(cx).worktree_for_id(note.worktree_id, cx) else {
+        return Task::ready(Vec::new());
+    };
+    let note_abs_path = worktree.read(cx).abs_path().join(note.path.as_std_path());
+    let known_stems = known_markdown_stems(project.read(cx), link_normalization, cx);
+
+    cx.background_spawn(async move {
+        let Ok(contents) = fs.load(&note_abs_path).await else {
+            return Vec::new();
+        };
+        scan_text_for_broken_links(&contents, &known_stems, link_normalization)
+    })
+}
+
+/// The notes that `note`'s own outgoing links resolve to, keyed by the same
+/// stem matching [`find_broken_links`] uses to find the ones that *don't*
+/// resolve. Used to detect mutual links: a backlink source that's also one
+/// of `note`'s own outgoing targets.
+pub fn find_outgoing_links(
+    project: Entity<Project>,
+    note: ProjectPath,
+    cx: &mut App,
+) -> Task<HashSet<ProjectPath>> {
+    let link_normalization = BacklinksSettings::get_global(cx).link_normalization;
+    let fs = project.read(cx).fs().clone();
+    let Some(worktree) = project.read(cx).worktree_for_id(note.worktree_id, cx) else {
+        return Task::ready(HashSet::default());
+    };
+    let note_abs_path = worktree.read(cx).abs_path().join(note.path.as_std_path());
+
+    let stem_targets: HashMap<String, ProjectPath> = project
+        .read(cx)
+        .visible_worktrees(cx)
+        .flat_map(|worktree| {
+            let worktree = worktree.read(cx);
+            let worktree_id = worktree.id();
+            worktree
+                .files(false, 0)
+                .filter(|entry| is_markdown_extension(&entry.path))
+                .filter_map(|entry| {
+                    entry.path.file_stem().map(|stem| {
+                        (
+                            stem.to_string(),
+                            ProjectPath {
+                                worktree_id,
+                                path: entry.path.clone(),
+                            },
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .map(|(stem, path)| {
+            let stem = match link_normalization {
+                LinkNormalizationMode::Strict => stem,
+                LinkNormalizationMode::Normalized => normalize_stem(&stem),
+            };
+            (stem, path)
+        })
+        .collect();
+
+    cx.background_spawn(async move {
+        let Ok(contents) = fs.load(&note_abs_path).await else {
+            return HashSet::default();
+        };
+        let mut targets = HashSet::default();
+        for line in contents.lines() {
+            for capture in MD_LINK_PATTERN.captures_iter(line) {
+                let Some(link_target) = capture.get(2) else {
+                    continue;
+                };
+                let Some(vault_target) = vault_link_target(link_target.as_str()) else {
+                    continue;
+                };
+                let link_stem = link_target_stem(vault_target);
+                let link_stem = match link_normalization {
+                    LinkNormalizationMode::Strict => link_stem,
+                    LinkNormalizationMode::Normalized => normalize_stem(&link_stem),
+                };
+                if let Some(path) = stem_targets.get(&link_stem) {
+                    targets.insert(path.clone());
+                }
+            }
+        }
+        targets
+    })
+}
+
+/// Builds a link string to `target` from `source`, in the style configured
+/// by `BacklinksSettings::link_syntax`, `link_path_format`, and
+/// `link_include_extension`. The single place link strings are generated
+/// from, so every generation point (the backlinks panel's "Copy Link" and
+/// "Replace reference" quick actions) stays consistent with the vault's
+/// preferred style.
+pub(crate) fn build_link(
+    target: &ProjectPath,
+    source: &ProjectPath,
+    settings: &BacklinksSettings,
+) -> String {
+    let path = note_link_path(target, source, settings);
+    let display_name = target.path.file_stem().unwrap_or_default();
+    match settings.link_syntax {
+        LinkSyntax::Wiki => format!("[[{path}]]"),
+        LinkSyntax::Markdown => format!("[{display_name}]({path})"),
+    }
+}
+
+/// The target portion of a link built by [`build_link`], per
+/// `BacklinksSettings::link_path_format`.
+fn note_link_path(
+    target: &ProjectPath,
+    source: &ProjectPath,
+    settings: &BacklinksSettings,
+) -> String {
+    match settings.link_path_format {
+        LinkPathFormat::Shortest => note_file_name(&target.path, settings.link_include_extension),
+        LinkPathFormat::Absolute => note_path_string(&target.path, settings.link_include_extension),
+        LinkPathFormat::Relative if target.worktree_id == source.worktree_id => {
+            let source_folder = source
+                .path
+                .parent()
+                .map(|parent| parent.into_arc())
+                .unwrap_or_else(util::rel_path::RelPath::empty_arc);
+            relative_note_path(&source_folder, &target.path, settings.link_include_extension)
+        }
+        // A relative path can't cross worktrees, so this falls back to the
+        // same full-path rendering as `Absolute`.
+        LinkPathFormat::Relative => note_path_string(&target.path, settings.link_include_extension),
+    }
+}
+
+/// `path`'s file name, without its directory, optionally stripping its
+/// extension.
+fn note_file_name(path: &util::rel_path::RelPath, include_extension: bool) -> String {
+    if include_extension {
+        path.file_name().unwrap_or_default().to_string()
+    } else {
+        path.file_stem().unwrap_or_default().to_string()
+    }
+}
+
+/// `path` rendered in full from the vault root, optionally stripping its
+/// extension.
+fn note_path_string(path: &util::rel_path::RelPath, include_extension: bool) -> String {
+    let displayed = path.display(util::paths::PathStyle::local()).to_string();
+    if include_extension {
+        return displayed;
+    }
+    match path.extension() {
+        Some(extension) => displayed
+            .strip_suffix(&format!(".{extension}"))
+            .unwrap_or(&displayed)
+            .to_string(),
+        None => displayed,
+    }
+}
+
+/// `target`'s path relative to `source_folder`, climbing out with `..` for
+/// every component of `source_folder` not shared with `target`'s directory.
+fn relative_note_path(
+    source_folder: &util::rel_path::RelPath,
+    target: &util::rel_path::RelPath,
+    include_extension: bool,
+) -> String {
+    let target_components: Vec<&str> = target.components().collect();
+    let source_components: Vec<&str> = source_folder.components().collect();
+    let target_dir_len = target_components.len().saturating_sub(1);
+    let common_len = source_components
+        .iter()
+        .zip(target_components.iter().take(target_dir_len))
+        .take_while(|(source_part, target_part)| source_part == target_part)
+        .count();
+    let mut parts: Vec<String> =
+        std::iter::repeat_n("..".to_string(), source_components.len() - common_len).collect();
+    parts.extend(
+        target_components[common_len..target_dir_len]
+            .iter()
+            .map(|component| component.to_string()),
+    );
+    parts.push(note_file_name(target, include_extension));
+    parts.join("/")
+}
+
+/// Synchronously reads `note`'s cached `field` frontmatter value from
+/// [`NOTE_IDENTITY_CACHE`], without touching the filesystem. Only populated
+/// once a [`find_backlinks`] or [`scan_vault_backlink_graph`] scan has run
+/// over `note`; returns `None` until then, the same as a note lacking the
+/// field.
+fn cached_note_identity(note: &ProjectPath, field: &'static str) -> Option<String> {
+    NOTE_IDENTITY_CACHE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&(note.clone(), field))
+        .and_then(|(_, value)| value.clone())
+}
+
+/// Resolves a wiki-link target name (the text between `[[` and `]]`, before
+/// any `|` alias) to the note it refers to. Used by
+/// [`backlinks_hover::BacklinkHoverProvider`] to turn a hover over
+/// `[[Note]]` into a concrete [`ProjectPath`] it can query backlinks for,
+/// without running a full link scan first.
+///
+/// Under `BacklinksSettings::note_identity`'s default,
+/// [`NoteIdentity::Filename`], matches by exact markdown file stem. Under
+/// [`NoteIdentity::Title`] or [`NoteIdentity::Id`], a note whose cached
+/// frontmatter field equals `name` matches first; since that cache is only
+/// populated by a prior scan (this lookup has to stay synchronous for
+/// hover), a note not yet scanned still falls back to a file stem match.
+pub fn resolve_note_by_name(project: &Project, name: &str, cx: &App) -> Option<ProjectPath> {
+    let identity_field = match BacklinksSettings::get_global(cx).note_identity {
+        NoteIdentity::Filename => None,
+        NoteIdentity::Title => Some("title"),
+        NoteIdentity::Id => Some("id"),
+    };
+    project.visible_worktrees(cx).find_map(|worktree| {
+        let worktree = worktree.read(cx);
+        let worktree_id = worktree.id();
+        worktree
+            .files(false, 0)
+            .find(|entry| {
+                if !is_markdown_extension(&entry.path) {
+                    return false;
+                }
+                if let Some(field) = identity_field {
+                    let project_path = ProjectPath {
+                        worktree_id,
+                        path: entry.path.clone(),
+                    };
+                    if cached_note_identity(&project_path, field).as_deref() == Some(name) {
+                        return true;
+                    }
+                }
+                entry.path.file_stem().is_some_and(|stem| stem.to_string() == name)
+            })
+            .map(|entry| ProjectPath {
+                worktree_id,
+                path: entry.path.clone(),
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use settings::DockPosition;
+
+    #[test]
+    fn decodes_url_encoded_spaces() {
+        assert_eq!(link_target_stem("My%20Note.md"), "My Note");
+    }
+
+    #[test]
+    fn decodes_url_encoded_parentheses() {
+        assert_eq!(link_target_stem("Notes%20%28Draft%29.md"), "Notes (Draft)");
+    }
+
+    #[test]
+    fn falls_back_to_raw_target_on_decode_failure() {
+        assert_eq!(link_target_stem("My%Note.md"), "My%Note");
+    }
+
+    #[test]
+    fn normalized_mode_ignores_case_and_hyphenation() {
+        assert!(stems_match(
+            "my-note",
+            "My Note",
+            LinkNormalizationMode::Normalized
+        ));
+        assert!(stems_match(
+            "My   Note",
+            "my_note",
+            LinkNormalizationMode::Normalized
+        ));
+    }
+
+    #[test]
+    fn strict_mode_requires_exact_match() {
+        assert!(!stems_match(
+            "my-note",
+            "My Note",
+            LinkNormalizationMode::Strict
+        ));
+        assert!(stems_match(
+            "My Note",
+            "My Note",
+            LinkNormalizationMode::Strict
+        ));
+    }
+
+    #[test]
+    fn literal_md_link_scan_matches_the_regex_path() {
+        let lines = [
+            "See [my note](My%20Note.md) for details.",
+            "Nothing to see here.",
+            "[](empty-text.md) and [two](links.md) and [three](here.md)",
+            "Unbalanced [brackets(with parens.md) stay unmatched",
+            "[]() is not a match: target must be non-empty",
+            "A [link](Note.md#Heading) with an anchor.",
+            "Trailing bracket with no target [oops]",
+        ];
+        for line in lines {
+            let regex_matches = md_link_matches(line, false);
+            let literal_matches = find_md_links_literal(line);
+            assert_eq!(
+                literal_matches, regex_matches,
+                "literal and regex scans disagree on {line:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn scan_text_for_broken_links_skips_known_stems() {
+        let known_stems: HashSet<String> = ["Other Note".to_string()].into_iter().collect();
+        let contents = "[ok](Other Note.md) but [missing](Ghost.md) is broken";
+        let broken_links =
+            scan_text_for_broken_links(contents, &known_stems, LinkNormalizationMode::Strict);
+        assert_eq!(broken_links.len(), 1);
+        assert_eq!(broken_links[0].link_text, "missing");
+        assert_eq!(broken_links[0].target, "Ghost.md");
+    }
+
+    #[test]
+    fn scan_text_for_broken_links_respects_normalization_mode() {
+        let known_stems: HashSet<String> = ["my note".to_string()].into_iter().collect();
+        let contents = "[link](My-Note.md)";
+        assert!(
+            scan_text_for_broken_links(contents, &known_stems, LinkNormalizationMode::Strict)
+                .len()
+                == 1,
+            "strict mode shouldn't match across hyphenation"
+        );
+        assert!(
+            scan_text_for_broken_links(contents, &known_stems, LinkNormalizationMode::Normalized)
+                .is_empty(),
+            "normalized mode should match across hyphenation"
+        );
+    }
+
+    #[test]
+    fn splits_heading_anchor_from_link_target() {
+        let (path, anchor) = split_anchor("Note.md#Heading");
+        assert_eq!(path, "Note.md");
+        assert_eq!(anchor, Some(LinkAnchor::Heading("Heading".to_string())));
+    }
+
+    #[test]
+    fn splits_block_anchor_from_link_target() {
+        let (path, anchor) = split_anchor("Note.md#^block-id");
+        assert_eq!(path, "Note.md");
+        assert_eq!(anchor, Some(LinkAnchor::Block("block-id".to_string())));
+    }
+
+    #[test]
+    fn no_anchor_when_link_target_has_no_hash() {
+        assert_eq!(split_anchor("Note.md"), ("Note.md", None));
+    }
+
+    #[test]
+    fn excludes_external_url_even_when_it_ends_in_a_note_name() {
+        assert_eq!(
+            vault_link_target("https://example.com/Note.md"),
+            None,
+            "a URL ending in a note's name isn't a reference to that note"
+        );
+        assert_eq!(vault_link_target("mailto:someone@example.com"), None);
+    }
+
+    #[test]
+    fn strips_file_scheme_and_matches_as_a_vault_path() {
+        assert_eq!(vault_link_target("file:///root/Note.md"), Some("root/Note.md"));
+    }
+
+    #[test]
+    fn passes_through_plain_relative_targets_unchanged() {
+        assert_eq!(vault_link_target("Note.md"), Some("Note.md"));
+        assert_eq!(vault_link_target("../notes/Note.md"), Some("../notes/Note.md"));
+    }
+
+    #[test]
+    fn collects_headings_and_block_ids() {
+        let contents = "# Title\n\nSome text ^my-block\n\n## Subheading\n";
+        let (headings, block_ids) = headings_and_block_ids(contents);
+        assert!(headings.contains("Title"));
+        assert!(headings.contains("Subheading"));
+        assert!(block_ids.contains("my-block"));
+    }
+
+    #[test]
+    fn detects_link_in_frontmatter_block() {
+        let contents = "---\nrelated: [Note](Note.md)\ntags: [a, b]\n---\n\n# Body\n";
+        let range = frontmatter_line_range(contents).expect("frontmatter block not detected");
+        assert_eq!(range, 0..4);
+        assert!(range.contains(&1));
+        assert!(!range.contains(&5));
+    }
+
+    #[test]
+    fn no_frontmatter_block_when_file_does_not_start_with_fence() {
+        assert_eq!(frontmatter_line_range("# Body\n\nSee [Note](Note.md)"), None);
+    }
+
+    #[test]
+    fn parses_plain_frontmatter_field() {
+        let contents = "---\nid: zettel-1234\ntitle: My Note\n---\n\n# Body\n";
+        assert_eq!(
+            parse_frontmatter_field(contents, "id"),
+            Some("zettel-1234".to_string())
+        );
+        assert_eq!(
+            parse_frontmatter_field(contents, "title"),
+            Some("My Note".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_quoted_frontmatter_field() {
+        let contents = "---\ntitle: \"My Note\"\n---\n\n# Body\n";
+        assert_eq!(
+            parse_frontmatter_field(contents, "title"),
+            Some("My Note".to_string())
+        );
+    }
+
+    #[test]
+    fn no_frontmatter_field_when_absent_or_no_frontmatter() {
+        assert_eq!(
+            parse_frontmatter_field("---\ntags: [a]\n---\n\n# Body\n", "title"),
+            None
+        );
+        assert_eq!(parse_frontmatter_field("# Body\n\nNo frontmatter", "title"), None);
+    }
+
+    #[test]
+    fn markdown_extension_matches_regardless_of_case() {
+        assert!(is_markdown_extension(&project_path("Note.md").path));
+        assert!(is_markdown_extension(&project_path("Note.MD").path));
+        assert!(is_markdown_extension(&project_path("Note.Md").path));
+        assert!(!is_markdown_extension(&project_path("Note.txt").path));
+    }
+
+    #[test]
+    fn context_truncation_is_noop_when_it_already_fits() {
+        let mut match_ranges = vec![4..8];
+        let context = truncate_context_centered("See [Note](Note.md)", &mut match_ranges, 120);
+        assert_eq!(context, "See [Note](Note.md)");
+        assert_eq!(match_ranges, vec![4..8]);
+    }
+
+    #[test]
+    fn context_truncation_disabled_when_max_length_is_zero() {
+        let mut match_ranges = vec![4..8];
+        let context = truncate_context_centered("See [Note](Note.md)", &mut match_ranges, 0);
+        assert_eq!(context, "See [Note](Note.md)");
+    }
+
+    fn test_target(stem: &str) -> (ProjectPath, String) {
+        let path = util::rel_path::RelPath::new(
+            std::path::Path::new(&format!("{stem}.md")),
+            util::paths::PathStyle::local(),
+        )
+        .unwrap()
+        .into_owned()
+        .into_arc();
+        (
+            ProjectPath {
+                worktree_id: project::WorktreeId::from_usize(0),
+                path,
+            },
+            stem.to_string(),
+        )
+    }
+
+    fn project_path(path: &str) -> ProjectPath {
+        let path = util::rel_path::RelPath::new(
+            std::path::Path::new(path),
+            util::paths::PathStyle::local(),
+        )
+        .unwrap()
+        .into_owned()
+        .into_arc();
+        ProjectPath {
+            worktree_id: project::WorktreeId::from_usize(0),
+            path,
+        }
+    }
+
+    #[test]
+    fn name_similarity_is_one_for_identical_stems() {
+        assert_eq!(name_similarity("Note", "note"), 1.0);
+    }
+
+    #[test]
+    fn name_similarity_is_zero_for_unrelated_stems() {
+        assert_eq!(name_similarity("Apple", "Zebra"), 0.0);
+    }
+
+    #[test]
+    fn relevance_score_prefers_same_folder_source() {
+        let target = project_path("projects/Note.md");
+        let same_folder = project_path("projects/Related.md");
+        let other_folder = project_path("archive/Related.md");
+        let (same_folder_depth, _) = relevance_score(&same_folder, &target);
+        let (other_folder_depth, _) = relevance_score(&other_folder, &target);
+        assert!(same_folder_depth > other_folder_depth);
+    }
+
+    #[test]
+    fn relevance_score_prefers_similar_names() {
+        let target = project_path("Note.md");
+        let similar = project_path("Notebook.md");
+        let dissimilar = project_path("Zebra.md");
+        let (_, similar_score) = relevance_score(&similar, &target);
+        let (_, dissimilar_score) = relevance_score(&dissimilar, &target);
+        assert!(similar_score > dissimilar_score);
+    }
+
+    #[test]
+    fn compiles_a_regex_per_target_substituting_the_placeholder() {
+        let targets = vec![test_target("Note")];
+        let compiled =
+            compile_custom_link_patterns(&["note:{name}".to_string()], &targets);
+        assert_eq!(compiled.len(), 1);
+        assert!(compiled[0].1.is_match("see note:Note for details"));
+        assert!(!compiled[0].1.is_match("see note:Other for details"));
+    }
+
+    #[test]
+    fn skips_invalid_custom_link_pattern_without_affecting_others() {
+        let targets = vec![test_target("Note")];
+        let compiled = compile_custom_link_patterns(
+            &["[{name}".to_string(), "note:{name}".to_string()],
+            &targets,
+        );
+        assert_eq!(compiled.len(), 1);
+    }
+
+    #[test]
+    fn resolves_ambiguous_stem_via_explicit_path_fragment() {
+        let source = project_path("Journal/2024-01-01.md");
+        let candidates = vec![project_path("Notes/Index.md"), project_path("Archive/Index.md")];
+        let resolved = resolve_ambiguous_stem("Archive/Index.md", &source, &candidates);
+        assert_eq!(resolved, Some(&candidates[1]));
+    }
+
+    #[test]
+    fn resolves_ambiguous_stem_via_same_folder_as_source() {
+        let source = project_path("Notes/Journal.md");
+        let candidates = vec![project_path("Notes/Index.md"), project_path("Archive/Index.md")];
+        let resolved = resolve_ambiguous_stem("Index.md", &source, &candidates);
+        assert_eq!(resolved, Some(&candidates[0]));
+    }
+
+    #[test]
+    fn truly_ambiguous_stem_has_no_resolution() {
+        let source = project_path("Journal/2024-01-01.md");
+        let candidates = vec![project_path("Notes/Index.md"), project_path("Archive/Index.md")];
+        assert_eq!(resolve_ambiguous_stem("Index.md", &source, &candidates), None);
+    }
+
+    #[test]
+    fn ambiguous_explicit_path_fragment_matching_several_candidates_has_no_resolution() {
+        let source = project_path("Journal/2024-01-01.md");
+        let candidates = vec![
+            project_path("Projects/Active/Index.md"),
+            project_path("Archive/Active/Index.md"),
+        ];
+        assert_eq!(resolve_ambiguous_stem("Active/Index.md", &source, &candidates), None);
+    }
+
+    #[test]
+    fn stem_group_key_respects_normalization_mode() {
+        assert_eq!(
+            stem_group_key("My-Note", LinkNormalizationMode::Normalized),
+            stem_group_key("my note", LinkNormalizationMode::Normalized)
+        );
+        assert_ne!(
+            stem_group_key("My-Note", LinkNormalizationMode::Strict),
+            stem_group_key("my note", LinkNormalizationMode::Strict)
+        );
+    }
+
+    #[test]
+    fn context_truncation_centers_on_the_first_match_and_shifts_ranges() {
+        let original =
+            "Some very long introductory text leading up to [Note](Note.md) and then some trailing text after it";
+        let match_start = original.find("[Note]").unwrap();
+        let match_end = original.find(')').unwrap() + 1;
+        let mut match_ranges = vec![match_start..match_end];
+        let context = truncate_context_centered(original, &mut match_ranges, 30);
+
+        assert!(context.starts_with('…'));
+        assert!(context.ends_with('…'));
+        assert_eq!(&context[match_ranges[0].clone()], "[Note](Note.md)");
+    }
+
+    #[test]
+    fn resolves_relative_link_within_same_directory() {
+        let source_abs_path = Path::new("/worktree/Notes/Journal.md");
+        assert_eq!(
+            resolve_relative_link_path("Index.md", source_abs_path),
+            Some(PathBuf::from("/worktree/Notes/Index.md"))
+        );
+    }
+
+    #[test]
+    fn resolves_relative_link_climbing_into_a_sibling_worktree() {
+        // `/worktree-a` and `/worktree-b` are two separate worktrees that
+        // happen to be sibling directories on disk, the way Zed's own
+        // worktrees usually are. A link from one that climbs out with `..`
+        // should resolve straight through into the other.
+        let source_abs_path = Path::new("/worktree-a/Notes/Journal.md");
+        assert_eq!(
+            resolve_relative_link_path("../../worktree-b/shared/Note.md", source_abs_path),
+            Some(PathBuf::from("/worktree-b/shared/Note.md"))
+        );
+    }
+
+    #[test]
+    fn relative_link_resolution_rejects_urls_and_absolute_paths() {
+        let source_abs_path = Path::new("/worktree/Notes/Journal.md");
+        assert_eq!(
+            resolve_relative_link_path("https://example.com/Note.md", source_abs_path),
+            None
+        );
+        assert_eq!(
+            resolve_relative_link_path("/etc/passwd", source_abs_path),
+            None
+        );
+    }
+
+    #[test]
+    fn relative_link_resolution_fails_closed_when_it_climbs_past_the_filesystem_root() {
+        let source_abs_path = Path::new("/Journal.md");
+        assert_eq!(resolve_relative_link_path("../Note.md", source_abs_path), None);
+    }
+
+    #[test]
+    fn recognizes_open_and_done_task_items_with_varied_bullets_and_indentation() {
+        assert_eq!(
+            parse_task_state("- [ ] follow up on [[Note]]"),
+            Some(TaskState::Open)
+        );
+        assert_eq!(
+            parse_task_state("* [x] follow up on [[Note]]"),
+            Some(TaskState::Done)
+        );
+        assert_eq!(
+            parse_task_state("  + [X] nested task mentioning [[Note]]"),
+            Some(TaskState::Done)
+        );
+    }
+
+    #[test]
+    fn ordinary_lines_and_malformed_checkboxes_have_no_task_state() {
+        assert_eq!(parse_task_state("Just a mention of [[Note]]"), None);
+        assert_eq!(parse_task_state("-[ ] missing space before bracket"), None);
+    }
+
+    fn test_settings(
+        link_syntax: LinkSyntax,
+        link_path_format: LinkPathFormat,
+        link_include_extension: bool,
+    ) -> BacklinksSettings {
+        BacklinksSettings {
+            dock: DockPosition::Right,
+            default_width: gpui::px(260.),
+            default_height: gpui::px(240.),
+            max_entries: 500,
+            include_self_references: false,
+            link_normalization: LinkNormalizationMode::Strict,
+            max_context_length: 120,
+            preserve_leading_indentation: false,
+            context_lines: 0,
+            custom_link_patterns: Vec::new(),
+            starts_open: false,
+            activation_priority: 10,
+            sort_order: BacklinksSortOrder::Location,
+            density: BacklinksDensity::Comfortable,
+            ambiguous_stem_matching: AmbiguousStemPolicy::Heuristic,
+            show_context: true,
+            open_external_for_unsupported_files: true,
+            on_click: BacklinkClickBehavior::Open,
+            exclude_from_scanning: false,
+            note_identity: NoteIdentity::Filename,
+            collapse_for_non_notes: false,
+            open_tasks_only: false,
+            scan_scope: BacklinkScanScope::Vault,
+            link_syntax,
+            link_path_format,
+            link_include_extension,
+        }
+    }
+
+    #[test]
+    fn build_link_wiki_shortest_omits_extension_and_folder() {
+        let settings = test_settings(LinkSyntax::Wiki, LinkPathFormat::Shortest, false);
+        let target = project_path("Archive/Note.md");
+        let source = project_path("Journal/Today.md");
+        assert_eq!(build_link(&target, &source, &settings), "[[Note]]");
+    }
+
+    #[test]
+    fn build_link_wiki_shortest_includes_extension_when_configured() {
+        let settings = test_settings(LinkSyntax::Wiki, LinkPathFormat::Shortest, true);
+        let target = project_path("Archive/Note.md");
+        let source = project_path("Journal/Today.md");
+        assert_eq!(build_link(&target, &source, &settings), "[[Note.md]]");
+    }
+
+    #[test]
+    fn build_link_markdown_absolute_renders_full_vault_path() {
+        let settings = test_settings(LinkSyntax::Markdown, LinkPathFormat::Absolute, false);
+        let target = project_path("Archive/Note.md");
+        let source = project_path("Journal/Today.md");
+        assert_eq!(
+            build_link(&target, &source, &settings),
+            "[Note](Archive/Note)"
+        );
+    }
+
+    #[test]
+    fn build_link_markdown_relative_climbs_to_a_sibling_folder() {
+        let settings = test_settings(LinkSyntax::Markdown, LinkPathFormat::Relative, false);
+        let target = project_path("Archive/Note.md");
+        let source = project_path("Journal/Today.md");
+        assert_eq!(
+            build_link(&target, &source, &settings),
+            "[Note](../Archive/Note)"
+        );
+    }
+
+    #[test]
+    fn build_link_relative_within_the_same_folder_has_no_leading_dots() {
+        let settings = test_settings(LinkSyntax::Wiki, LinkPathFormat::Relative, false);
+        let target = project_path("Journal/Note.md");
+        let source = project_path("Journal/Today.md");
+        assert_eq!(build_link(&target, &source, &settings), "[[Note]]");
+    }
+
+    #[test]
+    fn build_link_relative_falls_back_to_absolute_across_worktrees() {
+        let settings = test_settings(LinkSyntax::Wiki, LinkPathFormat::Relative, false);
+        let target = project_path("Archive/Note.md");
+        let mut source = project_path("Journal/Today.md");
+        source.worktree_id = project::WorktreeId::from_usize(1);
+        assert_eq!(build_link(&target, &source, &settings), "[[Archive/Note]]");
+    }
+}
@@ -0,0 +1,150 @@
+//! A workspace action that exports the whole vault's backlink graph to a
+//! JSON file at a path the user picks, separate from the per-note panel.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use fs::Fs;
+use futures::FutureExt as _;
+use gpui::{App, AppContext as _, Context, Window, actions};
+use util::ResultExt as _;
+use workspace::{Toast, Workspace, notifications::NotificationId};
+
+use crate::scan_vault_backlink_graph;
+
+actions!(
+    backlinks,
+    [
+        /// Exports the full backlink graph for every note in the project to
+        /// a JSON file at a path you choose.
+        ExportBacklinkGraph,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ExportBacklinkGraph, window, cx| {
+            export_backlink_graph(workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// How often the progress toast is refreshed while the scan runs.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+fn export_backlink_graph(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().clone();
+    let fs = project.read(cx).fs().clone();
+    let active_directory = workspace
+        .most_recent_active_path(cx)
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    let destination = cx.prompt_for_new_path(&active_directory, Some("backlinks.json"));
+
+    let notification_id = NotificationId::unique::<ExportBacklinkGraph>();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let scanned_files = Arc::new(AtomicUsize::new(0));
+    let (total_files, scan) =
+        scan_vault_backlink_graph(project, scanned_files.clone(), cancelled.clone(), cx);
+
+    workspace.show_toast(progress_toast(&notification_id, 0, total_files, &cancelled), cx);
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let mut scan = scan.fuse();
+        let nodes = loop {
+            let mut progress_timer = cx.background_executor().timer(PROGRESS_INTERVAL).fuse();
+            futures::select_biased! {
+                nodes = scan => break nodes,
+                _ = progress_timer => {
+                    let scanned = scanned_files.load(Ordering::Relaxed);
+                    workspace.update(cx, |workspace, cx| {
+                        workspace.show_toast(
+                            progress_toast(&notification_id, scanned, total_files, &cancelled),
+                            cx,
+                        );
+                    })?;
+                }
+            }
+        };
+
+        let Some(nodes) = nodes else {
+            workspace.update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(notification_id.clone(), "Backlink graph export cancelled")
+                        .autohide(),
+                    cx,
+                );
+            })?;
+            return anyhow::Ok(());
+        };
+
+        let destination = destination.await;
+        let Some(destination) = destination.log_err().and_then(|path| path.log_err()).flatten()
+        else {
+            workspace.update(cx, |workspace, cx| {
+                workspace.dismiss_toast(&notification_id, cx);
+            })?;
+            return anyhow::Ok(());
+        };
+
+        match write_backlink_graph(fs.as_ref(), destination.clone(), &nodes).await {
+            Ok(()) => {
+                workspace.update(cx, |workspace, cx| {
+                    workspace.show_toast(
+                        Toast::new(
+                            notification_id.clone(),
+                            format!("Exported backlink graph to {}", destination.display()),
+                        )
+                        .autohide(),
+                        cx,
+                    );
+                })?;
+            }
+            Err(error) => {
+                workspace.update(cx, |workspace, cx| {
+                    workspace.show_toast(
+                        Toast::new(notification_id.clone(), error.to_string()),
+                        cx,
+                    );
+                })?;
+            }
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Builds the toast shown while a scan is running, with a "Cancel" action
+/// that sets `cancelled`, which [`scan_vault_backlink_graph`] checks before
+/// submitting each further note, so the scan stops early.
+fn progress_toast(
+    notification_id: &NotificationId,
+    scanned: usize,
+    total: usize,
+    cancelled: &Arc<AtomicBool>,
+) -> Toast {
+    Toast::new(
+        notification_id.clone(),
+        format!("Scanning vault for backlinks… ({scanned}/{total})"),
+    )
+    .on_click("Cancel", {
+        let cancelled = cancelled.clone();
+        move |_window, _cx| cancelled.store(true, Ordering::Relaxed)
+    })
+}
+
+async fn write_backlink_graph(
+    fs: &dyn Fs,
+    destination: PathBuf,
+    nodes: &[crate::BacklinkGraphNode],
+) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(nodes)?;
+    fs.atomic_write(destination, json).await
+}
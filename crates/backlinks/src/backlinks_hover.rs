@@ -0,0 +1,284 @@
+//! A hover popover for `[[Note]]` wiki links, showing a note's backlink
+//! count and a few of its backlinks without leaving the editor.
+//!
+//! There's no language server backing this data, so it's wired in by
+//! decorating each editor's [`SemanticsProvider`] instead of registering a
+//! language server: [`BacklinkHoverProvider`] intercepts `hover` to check
+//! for a wiki link under the cursor first, falling back to the wrapped
+//! provider (LSP hover, etc.) for everything else.
+
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use collections::{HashMap, HashSet};
+use editor::{Editor, GotoDefinitionKind, SemanticsProvider};
+use gpui::{App, AppContext as _, Context, Entity, Task};
+use language::{Buffer, BufferId, BufferRow, Point, ToPoint as _};
+use project::lsp_store::{BufferSemanticTokens, CacheInlayHints, RefreshForServer};
+use project::{
+    DocumentHighlight, Hover, HoverBlock, HoverBlockKind, InlayHint, InvalidationStrategy,
+    LocationLink, Project, ProjectPath, ProjectTransaction,
+};
+use regex::Regex;
+
+use crate::{BacklinkTarget, find_backlinks, resolve_note_by_name};
+
+/// How long to wait, after the cursor settles on a wiki link, before
+/// scanning for its backlinks. Separate from `EditorSettings::hover_popover_delay`,
+/// which has already elapsed by the time `SemanticsProvider::hover` is
+/// called; this covers the scan itself, which is comparatively expensive.
+const HOVER_SCAN_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// How long a note's backlink summary is cached before a hover recomputes
+/// it, so bouncing the mouse across the same link repeatedly doesn't rescan
+/// the project every time.
+const SUMMARY_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Matches `[[Note]]` and `[[Note|Alias]]` wiki links.
+static WIKI_LINK_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[\[([^\]|]+)(?:\|[^\]]*)?\]\]").unwrap());
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|editor: &mut Editor, _, cx| {
+        register_backlink_hover(editor, cx);
+    })
+    .detach();
+}
+
+fn register_backlink_hover(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let Some(project) = editor.project().cloned() else {
+        return;
+    };
+    let Some(inner) = editor.semantics_provider() else {
+        return;
+    };
+    editor.set_semantics_provider(Some(Rc::new(BacklinkHoverProvider {
+        inner,
+        project,
+        cache: Rc::new(RefCell::new(HashMap::default())),
+    })));
+    let _ = cx;
+}
+
+/// A resolved note's backlink count and a few sample backlinks, cached so
+/// repeated hovers over the same link are instant.
+#[derive(Clone)]
+struct CachedSummary {
+    total_matches: usize,
+    sample_sources: Vec<ProjectPath>,
+}
+
+/// The maximum number of sample backlinks shown in the hover popover.
+const MAX_SAMPLE_BACKLINKS: usize = 3;
+
+struct BacklinkHoverProvider {
+    inner: Rc<dyn SemanticsProvider>,
+    project: Entity<Project>,
+    cache: Rc<RefCell<HashMap<ProjectPath, (Instant, CachedSummary)>>>,
+}
+
+impl BacklinkHoverProvider {
+    fn cached_summary(&self, note: &ProjectPath) -> Option<CachedSummary> {
+        let cache = self.cache.borrow();
+        let (cached_at, summary) = cache.get(note)?;
+        if cached_at.elapsed() > SUMMARY_CACHE_TTL {
+            return None;
+        }
+        Some(summary.clone())
+    }
+}
+
+impl SemanticsProvider for BacklinkHoverProvider {
+    fn hover(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        cx: &mut App,
+    ) -> Option<Task<Option<Vec<Hover>>>> {
+        let Some((range, name)) = wiki_link_at(buffer, position, cx) else {
+            return self.inner.hover(buffer, position, cx);
+        };
+        let Some(note) = resolve_note_by_name(self.project.read(cx), &name, cx) else {
+            return self.inner.hover(buffer, position, cx);
+        };
+
+        if let Some(summary) = self.cached_summary(&note) {
+            return Some(Task::ready(Some(vec![summary_hover_block(&summary, range)])));
+        }
+
+        let project = self.project.clone();
+        let cache = self.cache.clone();
+        Some(cx.spawn(async move |cx| {
+            cx.background_executor().timer(HOVER_SCAN_DEBOUNCE).await;
+            let scan = cx
+                .update(|cx| find_backlinks(project, BacklinkTarget::File(note.clone()), cx))
+                .ok()?;
+            let results = scan.await;
+            let summary = CachedSummary {
+                total_matches: results.total_matches,
+                sample_sources: results
+                    .entries
+                    .iter()
+                    .map(|entry| entry.source.clone())
+                    .take(MAX_SAMPLE_BACKLINKS)
+                    .collect(),
+            };
+            cache.borrow_mut().insert(note, (Instant::now(), summary.clone()));
+            Some(vec![summary_hover_block(&summary, range)])
+        }))
+    }
+
+    fn document_highlights(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        cx: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<DocumentHighlight>>>> {
+        self.inner.document_highlights(buffer, position, cx)
+    }
+
+    fn definitions(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        kind: GotoDefinitionKind,
+        cx: &mut App,
+    ) -> Option<Task<anyhow::Result<Option<Vec<LocationLink>>>>> {
+        self.inner.definitions(buffer, position, kind, cx)
+    }
+
+    fn supports_inlay_hints(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        self.inner.supports_inlay_hints(buffer, cx)
+    }
+
+    fn supports_semantic_tokens(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        self.inner.supports_semantic_tokens(buffer, cx)
+    }
+
+    fn inline_values(
+        &self,
+        buffer_handle: Entity<Buffer>,
+        range: Range<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<InlayHint>>>> {
+        self.inner.inline_values(buffer_handle, range, cx)
+    }
+
+    fn applicable_inlay_chunks(
+        &self,
+        buffer: &Entity<Buffer>,
+        ranges: &[Range<text::Anchor>],
+        cx: &mut App,
+    ) -> Vec<Range<BufferRow>> {
+        self.inner.applicable_inlay_chunks(buffer, ranges, cx)
+    }
+
+    fn invalidate_inlay_hints(&self, for_buffers: &HashSet<BufferId>, cx: &mut App) {
+        self.inner.invalidate_inlay_hints(for_buffers, cx);
+    }
+
+    fn inlay_hints(
+        &self,
+        invalidate: InvalidationStrategy,
+        buffer: Entity<Buffer>,
+        ranges: Vec<Range<text::Anchor>>,
+        known_chunks: Option<(clock::Global, HashSet<Range<BufferRow>>)>,
+        cx: &mut App,
+    ) -> Option<HashMap<Range<BufferRow>, Task<anyhow::Result<CacheInlayHints>>>> {
+        self.inner.inlay_hints(invalidate, buffer, ranges, known_chunks, cx)
+    }
+
+    fn semantic_tokens(
+        &self,
+        buffer: Entity<Buffer>,
+        refresh: Option<RefreshForServer>,
+        cx: &mut App,
+    ) -> Option<futures::future::Shared<Task<Result<BufferSemanticTokens, std::sync::Arc<anyhow::Error>>>>>
+    {
+        self.inner.semantic_tokens(buffer, refresh, cx)
+    }
+
+    fn range_for_rename(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        cx: &mut App,
+    ) -> Task<anyhow::Result<Option<Range<text::Anchor>>>> {
+        self.inner.range_for_rename(buffer, position, cx)
+    }
+
+    fn perform_rename(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        new_name: String,
+        cx: &mut App,
+    ) -> Option<Task<anyhow::Result<ProjectTransaction>>> {
+        self.inner.perform_rename(buffer, position, new_name, cx)
+    }
+}
+
+/// Finds the `[[Note]]`-style wiki link containing `position`, if any, in a
+/// markdown buffer. Returns the link's range and the note name it targets
+/// (the text before any `|` alias).
+fn wiki_link_at(
+    buffer: &Entity<Buffer>,
+    position: text::Anchor,
+    cx: &App,
+) -> Option<(Range<text::Anchor>, String)> {
+    let buffer = buffer.read(cx);
+    let is_markdown = buffer
+        .language()
+        .is_some_and(|language| language.name().as_ref() == "Markdown");
+    if !is_markdown {
+        return None;
+    }
+
+    let snapshot = buffer.snapshot();
+    let point = position.to_point(&snapshot);
+    let line_len = snapshot.line_len(point.row);
+    let line_start = Point::new(point.row, 0);
+    let line_end = Point::new(point.row, line_len);
+    let line_text = snapshot
+        .text_for_range(line_start..line_end)
+        .collect::<String>();
+
+    let column = point.column;
+    for capture in WIKI_LINK_PATTERN.captures_iter(&line_text) {
+        let whole = capture.get(0)?;
+        if (whole.start() as u32) > column || column > (whole.end() as u32) {
+            continue;
+        }
+        let name = capture.get(1)?.as_str().to_string();
+        let start = snapshot.anchor_before(Point::new(point.row, whole.start() as u32));
+        let end = snapshot.anchor_after(Point::new(point.row, whole.end() as u32));
+        return Some((start..end, name));
+    }
+    None
+}
+
+fn summary_hover_block(summary: &CachedSummary, range: Range<text::Anchor>) -> Hover {
+    let count_label = match summary.total_matches {
+        0 => "No backlinks".to_string(),
+        1 => "1 backlink".to_string(),
+        count => format!("{count} backlinks"),
+    };
+    let mut text = format!("**{count_label}**");
+    for source in &summary.sample_sources {
+        text.push_str(&format!(
+            "\n- `{}`",
+            source.path.display(util::paths::PathStyle::local())
+        ));
+    }
+    Hover {
+        contents: vec![HoverBlock {
+            text,
+            kind: HoverBlockKind::Markdown,
+        }],
+        range: Some(range),
+        language: None,
+    }
+}
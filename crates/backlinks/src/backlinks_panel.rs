@@ -0,0 +1,1394 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use editor::{Editor, EditorEvent};
+use fs::Fs;
+use gpui::{
+    Action, Animation, AnimationExt, App, AppContext as _, ClipboardItem, Context, Entity,
+    EventEmitter, FocusHandle, Focusable, KeyContext, Pixels, Render, ScrollHandle, Styled, Task,
+    WeakEntity, Window, actions, pulsating_between,
+};
+use project::ProjectPath;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use settings::Settings as _;
+use time::OffsetDateTime;
+use ui::{HighlightedLabel, Label, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+    item::ItemHandle,
+    pane::Pane,
+};
+
+use crate::{
+    BacklinkClickBehavior, BacklinkEntry, BacklinkTarget, BacklinksDensity, BacklinksSettings,
+    BrokenLink, TaskState, build_link, find_backlinks, find_broken_links, find_outgoing_links,
+    is_markdown_extension, known_markdown_stems, scan_text_for_broken_links,
+};
+
+/// How many of the most-referencing source files are shown in the reference
+/// density mini-map above the entries list.
+const TOP_REFERENCING_FILES: usize = 5;
+
+/// How many placeholder rows are shown in place of results while a scan is
+/// running and nothing has come back yet.
+const SKELETON_ROW_COUNT: usize = 4;
+
+/// How long to wait after an edit to the active note, while in
+/// [`BacklinksMode::BrokenLinks`], before rescanning its outgoing links.
+const BROKEN_LINKS_REPARSE_DEBOUNCE: Duration = Duration::from_millis(200);
+
+actions!(
+    backlinks,
+    [
+        /// Toggles the backlinks panel.
+        ToggleFocus,
+        /// Switches to the next mode tab, wrapping around from the last.
+        NextMode,
+        /// Switches to the previous mode tab, wrapping around from the first.
+        PrevMode,
+    ]
+);
+
+/// Jumps directly to the mode tab at the given index into [`MODE_ORDER`],
+/// the same left-to-right order the tabs are rendered in. Out-of-range
+/// indices are a no-op.
+#[derive(Clone, PartialEq, Debug, Deserialize, JsonSchema, Default, Action)]
+#[action(namespace = backlinks)]
+pub struct SetMode(pub usize);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<BacklinksPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Which set of links the panel is currently displaying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BacklinksMode {
+    /// Other notes that link to the active note.
+    Backlinks,
+    /// Other notes that link to any note in the active note's folder,
+    /// aggregated and grouped by which note each link resolves to.
+    FolderBacklinks,
+    /// Plain-text mentions of the active note's title that aren't hyperlinked.
+    /// Detection isn't implemented yet, so this mode always reports zero
+    /// results; it exists so the "Linked"/"Unlinked" split is in place for
+    /// when that scanner lands.
+    UnlinkedMentions,
+    /// The active note's own outgoing links that don't resolve to a file.
+    BrokenLinks,
+}
+
+/// The mode tabs in the left-to-right order they're rendered in, so
+/// [`NextMode`]/[`PrevMode`]/[`SetMode`] can cycle or jump between them the
+/// same way clicking through the header tabs would.
+const MODE_ORDER: [BacklinksMode; 4] = [
+    BacklinksMode::Backlinks,
+    BacklinksMode::UnlinkedMentions,
+    BacklinksMode::FolderBacklinks,
+    BacklinksMode::BrokenLinks,
+];
+
+/// Emitted by [`BacklinksPanel`] so other views (e.g. a future graph view)
+/// can stay in sync with what's selected without opening the file.
+#[derive(Debug, Clone)]
+pub enum Event {
+    SelectionChanged { source: ProjectPath },
+}
+
+/// Where a [`BacklinksMode::Backlinks`] or [`BacklinksMode::FolderBacklinks`]
+/// scan currently stands, so the empty state can tell a note that hasn't
+/// been scanned yet apart from one that's been confirmed to have zero
+/// backlinks, i.e. an orphan in the note graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BacklinkScanStatus {
+    /// A scan is in flight, or the active note hasn't been scanned yet.
+    Scanning,
+    /// The last completed scan found at least one backlink.
+    HasBacklinks,
+    /// The last completed scan found zero backlinks.
+    Orphan,
+}
+
+pub struct BacklinksPanel {
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    /// When set, the panel tracks the active item of this pane instead of the
+    /// workspace's global active item, so it can keep showing one note's
+    /// backlinks while the user edits in another split. Cleared automatically
+    /// if the pinned pane is closed.
+    pinned_pane: Option<WeakEntity<Pane>>,
+    active_note: Option<ProjectPath>,
+    /// Whether there's an active item, but it isn't a markdown file. Used
+    /// under `BacklinksSettings::collapse_for_non_notes` to tell that case
+    /// apart from nothing being open at all, which always gets the generic
+    /// "Open a note" message regardless of that setting.
+    non_note_active: bool,
+    mode: BacklinksMode,
+    entries: Vec<BacklinkEntry>,
+    /// Sources in `entries` that the active note also links back to, i.e. a
+    /// mutual link. Only populated in [`BacklinksMode::Backlinks`], since
+    /// "mutual with the active note" isn't well-defined once entries can
+    /// target different notes in [`BacklinksMode::FolderBacklinks`].
+    mutual_sources: collections::HashSet<ProjectPath>,
+    selected_index: Option<usize>,
+    /// Indices into `entries` whose context preview is expanded to show the
+    /// full, untruncated line. Cleared whenever `entries` is rebuilt, since a
+    /// refresh invalidates previous indices.
+    expanded_entries: collections::HashSet<usize>,
+    hidden_count: usize,
+    /// When the panel's entries last finished a `find_backlinks` scan for
+    /// `active_note`, for the debugging footer. `None` until the first scan
+    /// completes, or after `active_note` is cleared.
+    last_scanned: Option<time::OffsetDateTime>,
+    broken_links: Vec<BrokenLink>,
+    _refresh_task: Option<Task<()>>,
+    /// The active note's editor, while [`BacklinksMode::BrokenLinks`] has it
+    /// open, so its edit events can trigger a live rescan of `broken_links`
+    /// without waiting for a save or an `ActiveItemChanged` event. `None` in
+    /// every other mode, or when the active note isn't open in an editor.
+    outgoing_editor: Option<Entity<Editor>>,
+    _outgoing_editor_subscription: Option<gpui::Subscription>,
+    _broken_links_reparse_task: Option<Task<()>>,
+    /// Index into `entries` of the backlink currently being rewritten via the
+    /// "Replace reference" quick action, if any.
+    replace_target: Option<usize>,
+    replacement_editor: Entity<Editor>,
+    _replacement_editor_subscription: gpui::Subscription,
+    _workspace_subscription: gpui::Subscription,
+    entries_scroll_handle: ScrollHandle,
+    /// The entries list's scroll-child index of the first entry for each
+    /// source path, as of the last render. Used to scroll to a source's rows
+    /// when its bar is clicked in the reference density mini-map.
+    source_scroll_targets: collections::HashMap<ProjectPath, usize>,
+    /// Snapshotted from `BacklinksSettings::activation_priority` when the
+    /// panel is constructed, since [`Panel::activation_priority`] has no
+    /// access to `cx` to read the setting live.
+    activation_priority: u32,
+    focus_handle: FocusHandle,
+}
+
+impl BacklinksPanel {
+    pub fn new(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let replacement_editor = cx.new(|cx| Editor::single_line(window, cx));
+        let replacement_editor_subscription =
+            cx.subscribe_in(&replacement_editor, window, |this, _, event, _, cx| {
+                if matches!(event, EditorEvent::Blurred) {
+                    this.cancel_replace(cx);
+                }
+            });
+        let workspace_subscription = cx.subscribe_in(
+            &workspace.weak_handle().upgrade().expect("have a &mut Workspace"),
+            window,
+            |this, _workspace, event, window, cx| {
+                if let workspace::Event::ActiveItemChanged = event {
+                    this.refresh(window, cx);
+                }
+            },
+        );
+        Self {
+            workspace: workspace.weak_handle(),
+            fs: workspace.app_state().fs.clone(),
+            pinned_pane: None,
+            active_note: None,
+            non_note_active: false,
+            mode: BacklinksMode::Backlinks,
+            entries: Vec::new(),
+            mutual_sources: collections::HashSet::default(),
+            selected_index: None,
+            expanded_entries: collections::HashSet::default(),
+            hidden_count: 0,
+            last_scanned: None,
+            broken_links: Vec::new(),
+            _refresh_task: None,
+            outgoing_editor: None,
+            _outgoing_editor_subscription: None,
+            _broken_links_reparse_task: None,
+            replace_target: None,
+            replacement_editor,
+            _replacement_editor_subscription: replacement_editor_subscription,
+            _workspace_subscription: workspace_subscription,
+            entries_scroll_handle: ScrollHandle::new(),
+            source_scroll_targets: collections::HashMap::default(),
+            activation_priority: BacklinksSettings::get_global(cx).activation_priority,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    /// The `KeyContext` the panel dispatches actions in, so the mode
+    /// shortcuts in `assets/keymaps/*.json` only fire while it's focused.
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("BacklinksPanel");
+        dispatch_context
+    }
+
+    fn next_mode(&mut self, _: &NextMode, window: &mut Window, cx: &mut Context<Self>) {
+        let current_index = MODE_ORDER.iter().position(|mode| *mode == self.mode).unwrap_or(0);
+        let next_index = (current_index + 1) % MODE_ORDER.len();
+        self.set_mode(MODE_ORDER[next_index], window, cx);
+    }
+
+    fn prev_mode(&mut self, _: &PrevMode, window: &mut Window, cx: &mut Context<Self>) {
+        let current_index = MODE_ORDER.iter().position(|mode| *mode == self.mode).unwrap_or(0);
+        let prev_index = (current_index + MODE_ORDER.len() - 1) % MODE_ORDER.len();
+        self.set_mode(MODE_ORDER[prev_index], window, cx);
+    }
+
+    fn set_mode_action(&mut self, action: &SetMode, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(&mode) = MODE_ORDER.get(action.0) {
+            self.set_mode(mode, window, cx);
+        }
+    }
+
+    /// Where the current scan stands, derived from `entries`, the in-flight
+    /// refresh task, and `last_scanned`. Reuses `last_scanned` to tell a
+    /// freshly opened note apart from one that's been scanned and confirmed
+    /// to have zero backlinks.
+    fn scan_status(&self) -> BacklinkScanStatus {
+        if !self.entries.is_empty() {
+            BacklinkScanStatus::HasBacklinks
+        } else if self._refresh_task.is_some() || self.last_scanned.is_none() {
+            BacklinkScanStatus::Scanning
+        } else {
+            BacklinkScanStatus::Orphan
+        }
+    }
+
+    /// The sources referencing the active note most often, sorted by
+    /// descending occurrence count and capped at [`TOP_REFERENCING_FILES`].
+    fn top_referencing_files(&self) -> Vec<(ProjectPath, usize)> {
+        let mut counts: Vec<(ProjectPath, usize)> = Vec::new();
+        for entry in &self.entries {
+            match counts.iter_mut().find(|(source, _)| *source == entry.source) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((entry.source.clone(), 1)),
+            }
+        }
+        counts.sort_by(|(a_path, a_count), (b_path, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_path.cmp(b_path))
+        });
+        counts.truncate(TOP_REFERENCING_FILES);
+        counts
+    }
+
+    /// Scrolls the entries list to the first row referencing `source`, if
+    /// its position was recorded by the last render.
+    fn scroll_to_source(&mut self, source: &ProjectPath) {
+        if let Some(&index) = self.source_scroll_targets.get(source) {
+            self.entries_scroll_handle.scroll_to_item(index);
+        }
+    }
+
+    /// Toggles whether the panel tracks `pane`'s active item instead of the
+    /// workspace's global active item. Pinning the pane the backlinks panel
+    /// is already following unpins it, restoring the default behavior.
+    fn toggle_pin_to_pane(&mut self, pane: WeakEntity<Pane>, window: &mut Window, cx: &mut Context<Self>) {
+        self.pinned_pane = if self.pinned_pane.as_ref() == Some(&pane) {
+            None
+        } else {
+            Some(pane)
+        };
+        self.refresh(window, cx);
+    }
+
+    fn select_entry(&mut self, index: usize, cx: &mut Context<Self>) {
+        if self.selected_index == Some(index) {
+            return;
+        }
+        let Some(entry) = self.entries.get(index) else {
+            return;
+        };
+        self.selected_index = Some(index);
+        cx.emit(Event::SelectionChanged {
+            source: entry.source.clone(),
+        });
+        cx.notify();
+    }
+
+    /// Toggles whether `index`'s context preview shows its full, untruncated
+    /// line instead of the `BacklinksSettings::max_context_length`-clipped
+    /// one.
+    fn toggle_context_expanded(&mut self, index: usize, cx: &mut Context<Self>) {
+        if !self.expanded_entries.remove(&index) {
+            self.expanded_entries.insert(index);
+        }
+        cx.notify();
+    }
+
+    /// Opens a backlink entry's source file, recording the visit so
+    /// `BacklinksSortOrder::Frequency` can float it higher next time, and
+    /// jumps to the entry's line once it's open. `entry.line` is always
+    /// recorded regardless of `BacklinksSettings::show_context`, so this
+    /// works the same with the context preview disabled.
+    ///
+    /// Whether the entry opens in the active pane, as a preview tab, or in a
+    /// new split is governed by `BacklinksSettings::on_click`, unless
+    /// `modifiers` carries the platform's secondary click modifier, which
+    /// always forces a split.
+    ///
+    /// If `BacklinksSettings::open_external_for_unsupported_files` is set and
+    /// the entry's source isn't a markdown file, opens it with the OS's
+    /// default application instead of an editor.
+    fn open_backlink(
+        &mut self,
+        index: usize,
+        modifiers: gpui::Modifiers,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.entries.get(index).cloned() else {
+            return;
+        };
+        self.selected_index = Some(index);
+        cx.emit(Event::SelectionChanged {
+            source: entry.source.clone(),
+        });
+        crate::record_backlink_opened(&entry.abs_path, cx);
+        if BacklinksSettings::get_global(cx).open_external_for_unsupported_files
+            && !is_markdown_extension(&entry.source.path)
+        {
+            // Only markdown sources are ever rendered in an editor today; any
+            // other extension reaching here is one a broadened scan target
+            // list produced, and Zed has no renderer for it.
+            cx.open_with_system(&entry.abs_path);
+            cx.notify();
+            return;
+        }
+        let click_behavior = if modifiers.secondary() {
+            BacklinkClickBehavior::Split
+        } else {
+            BacklinksSettings::get_global(cx).on_click
+        };
+        let open_task = self.workspace.update_in(cx, |workspace, window, cx| {
+            match click_behavior {
+                BacklinkClickBehavior::Open => {
+                    workspace.open_path(entry.source.clone(), None, true, window, cx)
+                }
+                BacklinkClickBehavior::Preview => workspace.open_path_preview(
+                    entry.source.clone(),
+                    None,
+                    true,
+                    true,
+                    true,
+                    window,
+                    cx,
+                ),
+                BacklinkClickBehavior::Split => {
+                    workspace.split_path(entry.source.clone(), window, cx)
+                }
+            }
+        });
+        if let Ok(open_task) = open_task {
+            cx.spawn_in(window, async move |_this, cx| {
+                let item = open_task.await?;
+                let Some(editor) = item.downcast::<Editor>() else {
+                    return anyhow::Ok(());
+                };
+                editor.update_in(cx, |editor, window, cx| {
+                    editor.go_to_singleton_buffer_point(
+                        text::Point::new(entry.line, 0),
+                        window,
+                        cx,
+                    );
+                });
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+        }
+        cx.notify();
+    }
+
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: gpui::AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        workspace.update_in(&mut cx, |workspace, window, cx| {
+            cx.new(|cx| {
+                let mut panel = Self::new(workspace, window, cx);
+                panel.refresh(window, cx);
+                panel
+            })
+        })
+    }
+
+    fn set_mode(&mut self, mode: BacklinksMode, window: &mut Window, cx: &mut Context<Self>) {
+        if self.mode == mode {
+            return;
+        }
+        self.mode = mode;
+        self.refresh(window, cx);
+    }
+
+    fn refresh(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let active_item = match self.pinned_pane.as_ref().and_then(|pane| pane.upgrade()) {
+            Some(pane) => pane.read(cx).active_item(),
+            None => {
+                self.pinned_pane = None;
+                workspace.read(cx).active_item(cx)
+            }
+        };
+        let Some(active_note) = active_item.clone().and_then(|item| {
+            item.project_path(cx)
+                .filter(|path| is_markdown_extension(&path.path))
+        }) else {
+            self.active_note = None;
+            self.non_note_active = active_item.is_some();
+            self.entries.clear();
+            self.mutual_sources.clear();
+            self.selected_index = None;
+            self.expanded_entries.clear();
+            self.replace_target = None;
+            self.hidden_count = 0;
+            self.last_scanned = None;
+            self.broken_links.clear();
+            self.outgoing_editor = None;
+            self._outgoing_editor_subscription = None;
+            cx.notify();
+            return;
+        };
+        self.active_note = Some(active_note.clone());
+        self.non_note_active = false;
+        let project = workspace.read(cx).project().clone();
+        if self.mode != BacklinksMode::BrokenLinks {
+            self.outgoing_editor = None;
+            self._outgoing_editor_subscription = None;
+        }
+        match self.mode {
+            BacklinksMode::Backlinks | BacklinksMode::FolderBacklinks => {
+                let is_single_target = self.mode == BacklinksMode::Backlinks;
+                let target = if self.mode == BacklinksMode::FolderBacklinks {
+                    let folder = active_note
+                        .path
+                        .parent()
+                        .map(|parent| parent.into_arc())
+                        .unwrap_or_else(util::rel_path::RelPath::empty_arc);
+                    BacklinkTarget::Folder(ProjectPath {
+                        worktree_id: active_note.worktree_id,
+                        path: folder,
+                    })
+                } else {
+                    BacklinkTarget::File(active_note.clone())
+                };
+                let task = find_backlinks(project.clone(), target, cx);
+                let outgoing_task = if is_single_target {
+                    find_outgoing_links(project, active_note, cx)
+                } else {
+                    Task::ready(collections::HashSet::default())
+                };
+                self._refresh_task = Some(cx.spawn_in(window, async move |this, cx| {
+                    let (results, mutual_sources) = futures::join!(task, outgoing_task);
+                    this.update(cx, |this, cx| {
+                        this.entries = results.entries;
+                        this.mutual_sources = mutual_sources;
+                        this.selected_index = None;
+                        this.expanded_entries.clear();
+                        this.replace_target = None;
+                        this.hidden_count = results.hidden_count();
+                        this.last_scanned = Some(time::OffsetDateTime::now_utc());
+                        this._refresh_task = None;
+                        cx.notify();
+                    })
+                    .ok();
+                }));
+            }
+            BacklinksMode::UnlinkedMentions => {
+                self.entries.clear();
+                self.mutual_sources.clear();
+                self.selected_index = None;
+                self.expanded_entries.clear();
+                self.replace_target = None;
+                self.hidden_count = 0;
+                cx.notify();
+            }
+            BacklinksMode::BrokenLinks => {
+                self.sync_outgoing_editor(&active_item, window, cx);
+                let task = find_broken_links(project, active_note, cx);
+                self._refresh_task = Some(cx.spawn_in(window, async move |this, cx| {
+                    let broken_links = task.await;
+                    this.update(cx, |this, cx| {
+                        this.broken_links = broken_links;
+                        this._refresh_task = None;
+                        cx.notify();
+                    })
+                    .ok();
+                }));
+            }
+        }
+    }
+
+    /// Keeps [`Self::outgoing_editor`] in sync with whichever editor is
+    /// showing the active note in [`BacklinksMode::BrokenLinks`], subscribing
+    /// to its edit events so [`Self::broken_links`] can be rescanned live as
+    /// the user types. A no-op if `active_item` is already the subscribed
+    /// editor.
+    fn sync_outgoing_editor(
+        &mut self,
+        active_item: &Option<Box<dyn ItemHandle>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let editor = active_item.as_ref().and_then(|item| item.downcast::<Editor>());
+        if self.outgoing_editor == editor {
+            return;
+        }
+        self._outgoing_editor_subscription = editor.as_ref().map(|editor| {
+            cx.subscribe_in(
+                editor,
+                window,
+                |this, editor, event: &EditorEvent, window, cx| {
+                    if matches!(event, EditorEvent::Edited { .. } | EditorEvent::BufferEdited) {
+                        this.schedule_broken_links_reparse(editor.clone(), window, cx);
+                    }
+                },
+            )
+        });
+        self.outgoing_editor = editor;
+    }
+
+    /// Debounces a rescan of `editor`'s buffer text for [`BrokenLink`]s,
+    /// triggered by [`Self::sync_outgoing_editor`]'s subscription. Reparses
+    /// only `editor`'s own buffer, not the whole project, and reads it
+    /// directly from the buffer rather than through `fs`, so unsaved edits
+    /// are reflected immediately.
+    fn schedule_broken_links_reparse(
+        &mut self,
+        editor: Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self._broken_links_reparse_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(BROKEN_LINKS_REPARSE_DEBOUNCE).await;
+            this.update(cx, |this, cx| {
+                let Some(workspace) = this.workspace.upgrade() else {
+                    return;
+                };
+                let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+                    return;
+                };
+                let project = workspace.read(cx).project().clone();
+                let link_normalization = BacklinksSettings::get_global(cx).link_normalization;
+                let known_stems = known_markdown_stems(project.read(cx), link_normalization, cx);
+                let contents = buffer.read(cx).text();
+                this.broken_links =
+                    scan_text_for_broken_links(&contents, &known_stems, link_normalization);
+                this._broken_links_reparse_task = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Copies the distinct source file paths of the current backlinks to the
+    /// clipboard, one per line. When `with_lines` is set, copies every
+    /// occurrence instead, each as a `path:line` pair.
+    fn copy_backlink_paths(&mut self, with_lines: bool, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let text = if with_lines {
+            self.entries
+                .iter()
+                .map(|entry| format!("{}:{}", entry.abs_path.display(), entry.line + 1))
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else {
+            let mut seen = collections::HashSet::default();
+            self.entries
+                .iter()
+                .filter_map(|entry| {
+                    let path = entry.abs_path.display().to_string();
+                    seen.insert(path.clone()).then_some(path)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        cx.write_to_clipboard(ClipboardItem::new_string(text));
+    }
+
+    /// Copies a freshly generated link to the entry's target note, in the
+    /// vault's configured style (`BacklinksSettings::link_syntax`,
+    /// `link_path_format`, `link_include_extension`), rather than the
+    /// entry's current on-disk reference text.
+    fn copy_link(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(index) else {
+            return;
+        };
+        let link = build_link(&entry.target, &entry.source, BacklinksSettings::get_global(cx));
+        cx.write_to_clipboard(ClipboardItem::new_string(link));
+    }
+
+    /// Pins (or unpins, if already pinned) tracking to the workspace's
+    /// current active pane, so the panel keeps following that pane's active
+    /// item even while focus moves to a different split.
+    fn toggle_pin_to_active_pane(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(active_pane) = self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.active_pane().downgrade())
+            .ok()
+        else {
+            return;
+        };
+        self.toggle_pin_to_pane(active_pane, window, cx);
+    }
+
+    fn open_broken_link(&mut self, line: u32, cx: &mut Context<Self>) {
+        self.workspace
+            .update_in(cx, |workspace, window, cx| {
+                let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+                    return;
+                };
+                editor.update(cx, |editor, cx| {
+                    editor.go_to_singleton_buffer_point(text::Point::new(line, 0), window, cx);
+                });
+            })
+            .ok();
+    }
+
+    /// Starts the "Replace reference" quick action for `index`, focusing the
+    /// shared replacement editor pre-filled with a freshly generated link to
+    /// the entry's target note, in the vault's configured style
+    /// (`BacklinksSettings::link_syntax`), rather than the entry's current
+    /// on-disk reference text. Still just a starting point: the user can
+    /// edit it freely before confirming.
+    fn start_replace(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(index) else {
+            return;
+        };
+        let generated_link =
+            build_link(&entry.target, &entry.source, BacklinksSettings::get_global(cx));
+        self.replace_target = Some(index);
+        self.replacement_editor.update_in(cx, |editor, window, cx| {
+            editor.set_text(generated_link, window, cx);
+        });
+        self.replacement_editor
+            .read(cx)
+            .focus_handle(cx)
+            .focus(window, cx);
+        cx.notify();
+    }
+
+    fn cancel_replace(&mut self, cx: &mut Context<Self>) {
+        if self.replace_target.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Rewrites every matched occurrence on the entry's line to the text in
+    /// the replacement editor, going through the normal editor edit path so
+    /// the change is undoable like any other edit.
+    fn confirm_replace(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(index) = self.replace_target.take() else {
+            return;
+        };
+        let Some(entry) = self.entries.get(index).cloned() else {
+            return;
+        };
+        if entry.full_match_ranges.is_empty() {
+            return;
+        }
+        let replacement = self.replacement_editor.read(cx).text(cx);
+        let preserve_leading_indentation =
+            BacklinksSettings::get_global(cx).preserve_leading_indentation;
+        let open_task = self.workspace.update_in(cx, |workspace, window, cx| {
+            workspace.open_path(entry.source.clone(), None, true, window, cx)
+        });
+        let Ok(open_task) = open_task else {
+            return;
+        };
+        cx.spawn_in(window, async move |_this, cx| {
+            let item = open_task.await?;
+            let editor = item
+                .downcast::<Editor>()
+                .ok_or_else(|| anyhow::anyhow!("opened backlink source is not a text editor"))?;
+            editor.update_in(cx, |editor, window, cx| {
+                let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+                    return;
+                };
+                let snapshot = buffer.read(cx).snapshot();
+                let line_len = snapshot.line_len(entry.line);
+                let line_text: String = snapshot
+                    .text_for_range(
+                        text::Point::new(entry.line, 0)..text::Point::new(entry.line, line_len),
+                    )
+                    .collect();
+                // `full_match_ranges` are relative to the untruncated context,
+                // which only drops the line's indentation when it isn't preserved.
+                let leading_whitespace = if preserve_leading_indentation {
+                    0
+                } else {
+                    (line_text.len() - line_text.trim_start().len()) as u32
+                };
+                let edits = entry
+                    .full_match_ranges
+                    .iter()
+                    .map(|range| {
+                        let start = text::Point::new(
+                            entry.line,
+                            leading_whitespace + range.start as u32,
+                        );
+                        let end =
+                            text::Point::new(entry.line, leading_whitespace + range.end as u32);
+                        (start..end, replacement.clone())
+                    })
+                    .collect::<Vec<_>>();
+                editor.edit(edits, cx);
+            });
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl EventEmitter<PanelEvent> for BacklinksPanel {}
+impl EventEmitter<Event> for BacklinksPanel {}
+
+impl Focusable for BacklinksPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for BacklinksPanel {
+    fn persistent_name() -> &'static str {
+        "Backlinks Panel"
+    }
+
+    fn panel_key() -> &'static str {
+        "BacklinksPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        BacklinksSettings::get_global(cx).dock.into()
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.backlinks.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn default_size(&self, window: &Window, cx: &App) -> Pixels {
+        let settings = BacklinksSettings::get_global(cx);
+        match self.position(window, cx) {
+            DockPosition::Left | DockPosition::Right => settings.default_width,
+            DockPosition::Bottom => settings.default_height,
+        }
+    }
+
+    fn icon(&self, _: &Window, _: &App) -> Option<IconName> {
+        Some(IconName::Link)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Backlinks Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn starts_open(&self, _: &Window, cx: &App) -> bool {
+        BacklinksSettings::get_global(cx).starts_open
+    }
+
+    fn activation_priority(&self) -> u32 {
+        self.activation_priority
+    }
+}
+
+impl BacklinksPanel {
+    fn render_mode_tab(
+        &self,
+        id: &'static str,
+        label: &'static str,
+        mode: BacklinksMode,
+        count: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let active = self.mode == mode;
+        let text = match count {
+            Some(count) => format!("{label} ({count})"),
+            None => label.to_string(),
+        };
+        h_flex()
+            .id(id)
+            .cursor_pointer()
+            .flex_1()
+            .justify_center()
+            .py_1()
+            .border_b_1()
+            .when(active, |this| {
+                this.border_color(Color::Accent.color(cx))
+            })
+            .when(!active, |this| this.border_color(Color::Muted.color(cx)))
+            .child(Label::new(text).color(if active { Color::Default } else { Color::Muted }))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.set_mode(mode, window, cx);
+            }))
+    }
+
+    /// Rows shown in place of results while `_refresh_task` is running and no
+    /// entries have come back yet, so a scan of a large vault doesn't look
+    /// like a frozen, blank panel.
+    fn render_skeleton_rows(
+        &self,
+        density: BacklinksDensity,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let mut list = v_flex()
+            .gap_1()
+            .when(density == BacklinksDensity::Compact, |this| this.gap_0p5());
+        for index in 0..SKELETON_ROW_COUNT {
+            let width = rems(8.0 + (index % 3) as f32 * 4.0);
+            list = list.child(
+                ListItem::new(("backlink-skeleton", index))
+                    .spacing(if density == BacklinksDensity::Compact {
+                        ListItemSpacing::ExtraDense
+                    } else {
+                        ListItemSpacing::default()
+                    })
+                    .selectable(false)
+                    .disabled(true)
+                    .child(
+                        div()
+                            .h_3()
+                            .w(width)
+                            .rounded_sm()
+                            .bg(Color::Muted.color(cx))
+                            .with_animation(
+                                ("backlink-skeleton-shimmer", index),
+                                Animation::new(Duration::from_secs(1))
+                                    .repeat()
+                                    .with_easing(pulsating_between(0.3, 0.6)),
+                                |this, delta| this.opacity(delta),
+                            ),
+                    ),
+            );
+        }
+        list.into_any_element()
+    }
+
+    /// Renders a single backlink entry. In `Comfortable` density the source
+    /// path and context preview stack on separate lines (or sit side by side
+    /// in a fixed-width card when docked to the bottom); in `Compact`
+    /// density both are joined onto a single line with tighter spacing, to
+    /// fit more entries on screen. With `BacklinksSettings::show_context`
+    /// disabled, the context preview is skipped entirely and the source
+    /// label shows the line number instead.
+    fn render_backlink_entry(
+        &self,
+        index: usize,
+        entry: &BacklinkEntry,
+        docked_to_bottom: bool,
+        density: BacklinksDensity,
+        cx: &mut Context<Self>,
+    ) -> ListItem {
+        let show_context = BacklinksSettings::get_global(cx).show_context;
+        let source_label = h_flex()
+            .gap_1()
+            .child(Label::new(
+                entry.source.path.display(util::paths::PathStyle::local()).to_string(),
+            ))
+            .when(!show_context, |this| {
+                this.child(Label::new(format!(":{}", entry.line + 1)).color(Color::Muted))
+            })
+            .when(entry.from_unsaved_buffer, |this| {
+                this.child(
+                    Label::new("(unsaved buffer)")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .when_some(entry.task_state, |this, task_state| {
+                let (icon, color, label) = match task_state {
+                    TaskState::Open => (IconName::TodoPending, Color::Warning, "Open task"),
+                    TaskState::Done => (IconName::TodoComplete, Color::Success, "Completed task"),
+                };
+                this.child(Icon::new(icon).size(IconSize::Small).color(color))
+                    .tooltip(Tooltip::text(format!("{label} mention")))
+            })
+            .when(entry.anchor_valid == Some(false), |this| {
+                this.child(
+                    Icon::new(IconName::Warning)
+                        .size(IconSize::Small)
+                        .color(Color::Warning),
+                )
+                .tooltip(Tooltip::text("This link's anchor no longer exists"))
+            })
+            .when(entry.is_ambiguous, |this| {
+                this.child(
+                    Icon::new(IconName::Warning)
+                        .size(IconSize::Small)
+                        .color(Color::Warning),
+                )
+                .tooltip(Tooltip::text(
+                    "This link's target also matches another note; it couldn't be resolved with confidence",
+                ))
+            })
+            .when(self.mutual_sources.contains(&entry.source), |this| {
+                this.child(
+                    Label::new("↔ mutual")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .tooltip(Tooltip::text(
+                    "The active note also links back to this source",
+                ))
+            });
+        let entry_content = if !show_context {
+            source_label.into_any_element()
+        } else {
+            let context_label =
+                HighlightedLabel::from_ranges(entry.context.clone(), entry.match_ranges.clone())
+                    .color(Color::Muted);
+            if density == BacklinksDensity::Compact {
+                h_flex()
+                    .gap_1()
+                    .child(source_label)
+                    .child(Label::new(":").color(Color::Muted))
+                    .child(context_label.truncate())
+                    .into_any_element()
+            } else if docked_to_bottom {
+                // A short bottom dock doesn't have room for the source path
+                // stacked above the context line, so put them side by side in
+                // a fixed-width card instead.
+                h_flex()
+                    .w(rems(24.))
+                    .gap_2()
+                    .child(source_label)
+                    .child(context_label.truncate())
+                    .into_any_element()
+            } else {
+                let can_expand = entry.full_context != entry.context;
+                let expanded = can_expand && self.expanded_entries.contains(&index);
+                let context_row = if expanded {
+                    h_flex()
+                        .items_start()
+                        .gap_1()
+                        .child(
+                            IconButton::new(("toggle-context", index), IconName::ChevronUp)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Collapse"))
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.toggle_context_expanded(index, cx);
+                                })),
+                        )
+                        .child(
+                            HighlightedLabel::from_ranges(
+                                entry.full_context.clone(),
+                                entry.full_match_ranges.clone(),
+                            )
+                            .color(Color::Muted),
+                        )
+                } else {
+                    h_flex().items_start().gap_1().when(can_expand, |this| {
+                        this.child(
+                            IconButton::new(("toggle-context", index), IconName::ChevronDown)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Show full line"))
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.toggle_context_expanded(index, cx);
+                                })),
+                        )
+                    })
+                    .child(context_label)
+                };
+                // With `BacklinksSettings::context_lines` set, the matched
+                // line is shown centered between its dimmed neighbors
+                // instead of alone, so a match embedded in a long wrapped
+                // paragraph still reads in context.
+                let context_column = v_flex()
+                    .children(
+                        entry
+                            .context_before
+                            .iter()
+                            .map(|line| Label::new(line.clone()).color(Color::Hidden).truncate()),
+                    )
+                    .child(context_row)
+                    .children(
+                        entry
+                            .context_after
+                            .iter()
+                            .map(|line| Label::new(line.clone()).color(Color::Hidden).truncate()),
+                    );
+                v_flex().child(source_label).child(context_column).into_any_element()
+            }
+        };
+        ListItem::new(("backlink-entry", index))
+            .spacing(if density == BacklinksDensity::Compact {
+                ListItemSpacing::ExtraDense
+            } else {
+                ListItemSpacing::default()
+            })
+            .toggle_state(self.selected_index == Some(index))
+            .child(entry_content)
+            .when(show_context, |this| {
+                this.end_slot(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            IconButton::new(("copy-link", index), IconName::Copy)
+                                .tooltip(Tooltip::text("Copy Link"))
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.copy_link(index, cx);
+                                })),
+                        )
+                        .child(
+                            IconButton::new(("replace-reference", index), IconName::Replace)
+                                .tooltip(Tooltip::text("Replace reference"))
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.start_replace(index, window, cx);
+                                })),
+                        ),
+                )
+            })
+            .on_click(cx.listener(move |this, event: &gpui::ClickEvent, window, cx| {
+                if event.click_count() > 1 {
+                    this.open_backlink(index, event.modifiers(), window, cx);
+                } else {
+                    this.select_entry(index, cx);
+                }
+            }))
+    }
+}
+
+impl Render for BacklinksPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let collapsed_for_non_note =
+            self.non_note_active && BacklinksSettings::get_global(cx).collapse_for_non_notes;
+        if collapsed_for_non_note {
+            return v_flex()
+                .size_full()
+                .items_center()
+                .justify_center()
+                .child(Label::new("Backlinks apply to notes.").color(Color::Muted));
+        }
+        let docked_to_bottom = self.position(window, cx) == DockPosition::Bottom;
+        let linked_count =
+            (self.mode == BacklinksMode::Backlinks).then_some(self.entries.len());
+        let tabs = h_flex()
+            .w_full()
+            .child(self.render_mode_tab(
+                "backlinks-tab",
+                "Linked",
+                BacklinksMode::Backlinks,
+                linked_count,
+                cx,
+            ))
+            .child(self.render_mode_tab(
+                "unlinked-mentions-tab",
+                "Unlinked",
+                BacklinksMode::UnlinkedMentions,
+                Some(0),
+                cx,
+            ))
+            .child(self.render_mode_tab(
+                "folder-backlinks-tab",
+                "Folder",
+                BacklinksMode::FolderBacklinks,
+                None,
+                cx,
+            ))
+            .child(self.render_mode_tab(
+                "broken-links-tab",
+                "Broken Links",
+                BacklinksMode::BrokenLinks,
+                None,
+                cx,
+            ))
+            .child(
+                IconButton::new("pin-to-active-pane", IconName::Pin)
+                    .toggle_state(self.pinned_pane.is_some())
+                    .tooltip(Tooltip::text(if self.pinned_pane.is_some() {
+                        "Unpin From Pane"
+                    } else {
+                        "Pin to Active Pane"
+                    }))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_pin_to_active_pane(window, cx);
+                    })),
+            )
+            .when(
+                matches!(
+                    self.mode,
+                    BacklinksMode::Backlinks | BacklinksMode::FolderBacklinks
+                ) && !self.entries.is_empty(),
+                |this| {
+                    this.child(
+                        IconButton::new("copy-backlink-paths", IconName::Copy)
+                            .tooltip(Tooltip::text(
+                                "Copy Backlink Paths (hold cmd/ctrl for path:line pairs)",
+                            ))
+                            .on_click(cx.listener(|this, event: &gpui::ClickEvent, _, cx| {
+                                this.copy_backlink_paths(event.modifiers().secondary(), cx);
+                            })),
+                    )
+                },
+            );
+
+        let reference_density = matches!(
+            self.mode,
+            BacklinksMode::Backlinks | BacklinksMode::FolderBacklinks
+        )
+        .then(|| self.top_referencing_files())
+        .filter(|top_files| !top_files.is_empty())
+        .map(|top_files| {
+            let max_count = top_files
+                .iter()
+                .map(|(_, count)| *count)
+                .max()
+                .unwrap_or(1)
+                .max(1);
+            let mut bars = v_flex().gap_1().p_2().border_b_1().border_color(Color::Muted.color(cx));
+            for (source, count) in top_files {
+                let label = source
+                    .path
+                    .file_name()
+                    .map(ToOwned::to_owned)
+                    .unwrap_or_else(|| source.path.display(util::paths::PathStyle::local()).into_owned());
+                bars = bars.child(
+                    h_flex()
+                        .id(format!("reference-density-bar-{}", source.path.as_unix_str()))
+                        .w_full()
+                        .gap_2()
+                        .cursor_pointer()
+                        .child(
+                            div()
+                                .w(rems(8.))
+                                .flex_shrink_0()
+                                .child(Label::new(label).size(LabelSize::Small).truncate()),
+                        )
+                        .child(
+                            div()
+                                .h_1()
+                                .rounded_sm()
+                                .bg(Color::Accent.color(cx))
+                                .w(relative(count as f32 / max_count as f32))
+                                .flex_1(),
+                        )
+                        .child(Label::new(count.to_string()).size(LabelSize::Small).color(Color::Muted))
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.scroll_to_source(&source);
+                            cx.notify();
+                        })),
+                );
+            }
+            bars
+        });
+
+        let content = if self.active_note.is_none() {
+            v_flex()
+                .child(Label::new("Open a note to see its backlinks.").color(Color::Muted))
+                .into_any_element()
+        } else {
+            match self.mode {
+                BacklinksMode::Backlinks | BacklinksMode::FolderBacklinks => {
+                    match self.scan_status() {
+                        BacklinkScanStatus::Scanning => {
+                            self.render_skeleton_rows(BacklinksSettings::get_global(cx).density, cx)
+                        }
+                        BacklinkScanStatus::Orphan if self.mode == BacklinksMode::Backlinks => {
+                            v_flex()
+                                .child(
+                                    Label::new("This note is an orphan (0 backlinks).")
+                                        .color(Color::Muted),
+                                )
+                                .child(
+                                    Label::new(
+                                        "Link to it from another note to connect it to the vault.",
+                                    )
+                                    .color(Color::Muted),
+                                )
+                                .into_any_element()
+                        }
+                        BacklinkScanStatus::Orphan => v_flex()
+                            .child(Label::new("No backlinks found.").color(Color::Muted))
+                            .into_any_element(),
+                        BacklinkScanStatus::HasBacklinks => {
+                            let density = BacklinksSettings::get_global(cx).density;
+                            let mut list = if docked_to_bottom { h_flex() } else { v_flex() }
+                                .id("backlinks-entries")
+                                .flex_1()
+                                .when(docked_to_bottom, |this| this.overflow_x_scroll())
+                                .when(!docked_to_bottom, |this| this.overflow_y_scroll())
+                                .track_scroll(&self.entries_scroll_handle)
+                                .gap_1();
+                            let mut last_target: Option<&ProjectPath> = None;
+                            let mut scroll_child_index = 0;
+                            self.source_scroll_targets.clear();
+                            for (index, entry) in self.entries.iter().enumerate() {
+                                if self.mode == BacklinksMode::FolderBacklinks
+                                    && last_target != Some(&entry.target)
+                                {
+                                    last_target = Some(&entry.target);
+                                    list = list.child(
+                                        Label::new(
+                                            entry
+                                                .target
+                                                .path
+                                                .display(util::paths::PathStyle::local())
+                                                .to_string(),
+                                        )
+                                        .size(LabelSize::Small)
+                                        .color(Color::Default),
+                                    );
+                                    scroll_child_index += 1;
+                                }
+                                self.source_scroll_targets
+                                    .entry(entry.source.clone())
+                                    .or_insert(scroll_child_index);
+                                if self.replace_target == Some(index) {
+                                    list = list.child(
+                                        ListItem::new(("backlink-entry", index)).child(
+                                            h_flex()
+                                                .w_full()
+                                                .gap_1()
+                                                .child(
+                                                    div()
+                                                        .flex_1()
+                                                        .child(self.replacement_editor.clone()),
+                                                )
+                                                .child(
+                                                    IconButton::new(
+                                                        ("confirm-replace", index),
+                                                        IconName::Check,
+                                                    )
+                                                    .tooltip(Tooltip::text("Confirm replacement"))
+                                                    .on_click(cx.listener(
+                                                        |this, _, window, cx| {
+                                                            this.confirm_replace(window, cx);
+                                                        },
+                                                    )),
+                                                )
+                                                .child(
+                                                    IconButton::new(
+                                                        ("cancel-replace", index),
+                                                        IconName::Close,
+                                                    )
+                                                    .tooltip(Tooltip::text("Cancel replacement"))
+                                                    .on_click(cx.listener(
+                                                        |this, _, _, cx| {
+                                                            this.cancel_replace(cx);
+                                                        },
+                                                    )),
+                                                ),
+                                        ),
+                                    );
+                                    scroll_child_index += 1;
+                                    continue;
+                                }
+                                list = list.child(self.render_backlink_entry(
+                                    index,
+                                    entry,
+                                    docked_to_bottom,
+                                    density,
+                                    cx,
+                                ));
+                                scroll_child_index += 1;
+                            }
+                            if self.hidden_count > 0 {
+                                list = list.child(
+                                    Label::new(format!(
+                                        "{} more not shown — refine or open in multibuffer",
+                                        self.hidden_count
+                                    ))
+                                    .color(Color::Muted),
+                                );
+                            }
+                            list.into_any_element()
+                        }
+                    }
+                }
+                BacklinksMode::UnlinkedMentions => v_flex()
+                    .child(
+                        Label::new("Unlinked mention detection isn't implemented yet.")
+                            .color(Color::Muted),
+                    )
+                    .into_any_element(),
+                BacklinksMode::BrokenLinks => {
+                    if self.broken_links.is_empty() && self._refresh_task.is_some() {
+                        self.render_skeleton_rows(BacklinksSettings::get_global(cx).density, cx)
+                    } else if self.broken_links.is_empty() {
+                        v_flex()
+                            .child(Label::new("No broken links found.").color(Color::Muted))
+                            .into_any_element()
+                    } else {
+                        let mut list = v_flex().gap_1();
+                        for broken_link in &self.broken_links {
+                            let line = broken_link.line;
+                            list = list.child(
+                                ListItem::new(("broken-link", line as usize))
+                                    .child(
+                                        v_flex()
+                                            .child(Label::new(format!(
+                                                "Line {}: {}",
+                                                line + 1,
+                                                broken_link.link_text
+                                            )))
+                                            .child(
+                                                Label::new(broken_link.target.clone())
+                                                    .color(Color::Muted),
+                                            ),
+                                    )
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.open_broken_link(line, cx);
+                                    })),
+                            );
+                        }
+                        list.into_any_element()
+                    }
+                }
+            }
+        };
+
+        let last_scanned_footer = self.last_scanned.map(|scanned_at| {
+            let now = OffsetDateTime::now_utc();
+            let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
+            let relative = time_format::format_localized_timestamp(
+                scanned_at,
+                now,
+                local_offset,
+                time_format::TimestampFormat::Relative,
+            );
+            let absolute = time_format::format_localized_timestamp(
+                scanned_at,
+                now,
+                local_offset,
+                time_format::TimestampFormat::EnhancedAbsolute,
+            );
+            h_flex().p_1().border_t_1().border_color(Color::Muted.color(cx)).child(
+                Label::new(format!("Last scanned {relative}"))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .tooltip(Tooltip::text(absolute))
+        });
+
+        v_flex()
+            .size_full()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::next_mode))
+            .on_action(cx.listener(Self::prev_mode))
+            .on_action(cx.listener(Self::set_mode_action))
+            .child(tabs)
+            .when_some(reference_density, |this, reference_density| {
+                this.child(reference_density)
+            })
+            .child(v_flex().size_full().p_2().child(content))
+            .when_some(last_scanned_footer, |this, footer| this.child(footer))
+    }
+}
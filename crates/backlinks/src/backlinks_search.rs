@@ -0,0 +1,122 @@
+use anyhow::Result;
+use collections::HashMap;
+use editor::{PathKey, multibuffer_context_lines};
+use gpui::{
+    App, AppContext as _, AsyncWindowContext, Context, Entity, TaskExt, WeakEntity, Window, actions,
+};
+use project::{Project, ProjectPath};
+use search::project_search::{ProjectSearch, ProjectSearchView};
+use workspace::Workspace;
+
+use crate::{BacklinkEntry, BacklinkResults, BacklinkTarget, find_backlinks, is_markdown_extension};
+
+actions!(
+    backlinks,
+    [
+        /// Runs a backlinks scan for the active note and shows the matches in
+        /// a project search results view, for the familiar search navigation
+        /// (next/previous match, fold/unfold, replace) instead of the
+        /// dedicated panel.
+        FindBacklinksInSearch,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &FindBacklinksInSearch, window, cx| {
+            find_backlinks_in_search(workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+fn find_backlinks_in_search(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(active_note) = workspace
+        .active_item(cx)
+        .and_then(|item| item.project_path(cx))
+        .filter(|path| is_markdown_extension(&path.path))
+    else {
+        return;
+    };
+    let project = workspace.project().clone();
+    let task = find_backlinks(project.clone(), BacklinkTarget::File(active_note), cx);
+    cx.spawn_in(window, async move |workspace, cx| {
+        let results = task.await;
+        if results.entries.is_empty() {
+            return anyhow::Ok(());
+        }
+        show_backlinks_in_search(workspace, project, results, cx).await
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Feeds `results` into a fresh [`ProjectSearch`]'s multibuffer, exactly the
+/// way [`search::project_search`] itself populates one from a live query, so
+/// the resulting view gets the same navigation, folding, and replace support
+/// as an ordinary project-wide search.
+async fn show_backlinks_in_search(
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    results: BacklinkResults,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let mut entries_by_source: HashMap<ProjectPath, Vec<BacklinkEntry>> = HashMap::default();
+    for entry in results.entries {
+        entries_by_source
+            .entry(entry.source.clone())
+            .or_default()
+            .push(entry);
+    }
+
+    let project_search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+    let context_line_count = cx.update(|_window, cx| multibuffer_context_lines(cx))?;
+
+    for (source, entries) in entries_by_source {
+        let buffer = project
+            .update(cx, |project, cx| project.open_buffer(source, cx))
+            .await?;
+        let path_key = cx.update(|_window, cx| PathKey::for_buffer(&buffer, cx))?;
+        let ranges = cx.update(|_window, cx| {
+            let buffer_snapshot = buffer.read(cx).snapshot();
+            entries
+                .iter()
+                .map(|entry| {
+                    let line_len = buffer_snapshot.line_len(entry.line);
+                    buffer_snapshot.anchor_before(text::Point::new(entry.line, 0))
+                        ..buffer_snapshot.anchor_after(text::Point::new(entry.line, line_len))
+                })
+                .collect::<Vec<_>>()
+        })?;
+        let new_ranges = project_search
+            .update(cx, |project_search, cx| {
+                project_search.excerpts.update(cx, |excerpts, cx| {
+                    excerpts.set_anchored_excerpts_for_path(
+                        path_key,
+                        buffer,
+                        ranges,
+                        context_line_count,
+                        cx,
+                    )
+                })
+            })
+            .await;
+        project_search.update(cx, |project_search, cx| {
+            project_search.match_ranges.extend(new_ranges);
+            cx.notify();
+        });
+    }
+
+    workspace.update_in(cx, |workspace, window, cx| {
+        let weak_workspace = cx.entity().downgrade();
+        let view = cx.new(|cx| {
+            ProjectSearchView::new(weak_workspace, project_search, window, cx, None)
+        });
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    })?;
+
+    Ok(())
+}
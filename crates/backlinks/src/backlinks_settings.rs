@@ -0,0 +1,139 @@
+use gpui::{App, Pixels};
+pub use settings::{
+    AmbiguousStemPolicy, BacklinkClickBehavior, BacklinkScanScope, BacklinksDensity,
+    BacklinksSortOrder, DockPosition, LinkNormalizationMode, LinkPathFormat, LinkSyntax,
+    NoteIdentity,
+};
+use settings::{RegisterSetting, Settings, SettingsContent, SettingsLocation, WorktreeId};
+use util::rel_path::RelPath;
+
+/// Settings for the backlinks panel.
+#[derive(Debug, Clone, RegisterSetting)]
+pub struct BacklinksSettings {
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    /// The panel's height when docked at the bottom.
+    pub default_height: Pixels,
+    /// The maximum number of backlink entries to collect and display for a
+    /// single note, to keep heavily-referenced hub notes from making the
+    /// panel sluggish.
+    pub max_entries: usize,
+    /// Whether a note's links to itself should be reported as backlinks of
+    /// that same note.
+    pub include_self_references: bool,
+    /// How strictly link targets must match a file name to be recognized.
+    pub link_normalization: LinkNormalizationMode,
+    /// The maximum number of characters to show in a backlink's line-context
+    /// preview, centered on the matched link. `0` disables truncation.
+    pub max_context_length: usize,
+    /// Whether a backlink's line-context preview preserves the line's
+    /// leading indentation, instead of trimming it.
+    pub preserve_leading_indentation: bool,
+    /// The number of extra lines to show before and after a backlink's
+    /// matched line in its context preview, centered on the match. `0`
+    /// preserves the single-line preview.
+    pub context_lines: usize,
+    /// Extra regex templates for recognizing custom mention syntaxes as
+    /// backlinks. `{name}` is substituted with the target note's escaped
+    /// file stem before compiling.
+    pub custom_link_patterns: Vec<String>,
+    /// Whether the backlinks panel should open on startup.
+    pub starts_open: bool,
+    /// Where the backlinks panel's icon ranks among other panel icons in the
+    /// status bar.
+    pub activation_priority: u32,
+    /// How backlink entries are ordered within the panel.
+    pub sort_order: BacklinksSortOrder,
+    /// How much vertical space each backlink entry takes up in the panel.
+    pub density: BacklinksDensity,
+    /// How to resolve a link whose target stem matches more than one note in
+    /// the project.
+    pub ambiguous_stem_matching: AmbiguousStemPolicy,
+    /// Whether to collect and show each backlink's line-context preview.
+    pub show_context: bool,
+    /// Whether opening a backlink entry whose source file Zed can't render in
+    /// an editor falls back to opening it with the OS's default application.
+    pub open_external_for_unsupported_files: bool,
+    /// What clicking a backlink entry does, absent any modifier key. This is
+    /// independent of the wiki-link hover summary registered by
+    /// `backlinks_hover`, which shows without any click at all and is
+    /// unaffected by this setting.
+    pub on_click: BacklinkClickBehavior,
+    /// Whether the worktree this value was resolved for is excluded from
+    /// backlink scans. Meant to be read per-worktree through
+    /// [`Self::is_worktree_excluded`] rather than via [`Settings::get_global`],
+    /// since it's set in a worktree's local settings rather than globally.
+    pub exclude_from_scanning: bool,
+    /// What a link target or `[[wikilink]]` name is matched against.
+    pub note_identity: NoteIdentity,
+    /// Whether the panel shows a minimal "Backlinks apply to notes" hint
+    /// instead of its normal tabs and entry list when the active item isn't a
+    /// markdown file, rather than running a scan for it.
+    pub collapse_for_non_notes: bool,
+    /// Whether to only show backlinks found on open (`- [ ]`) task list
+    /// items, hiding both prose mentions and completed (`- [x]`) tasks.
+    pub open_tasks_only: bool,
+    /// Which files are scanned as candidate backlink sources, relative to
+    /// the active note's folder.
+    pub scan_scope: BacklinkScanScope,
+    /// The link syntax used when generating a new link to a note.
+    pub link_syntax: LinkSyntax,
+    /// How much of the target note's path a generated link includes.
+    pub link_path_format: LinkPathFormat,
+    /// Whether a generated link includes the target note's file extension.
+    pub link_include_extension: bool,
+}
+
+impl Settings for BacklinksSettings {
+    fn from_settings(content: &SettingsContent) -> Self {
+        let backlinks = content.backlinks.as_ref().unwrap();
+        Self {
+            dock: backlinks.dock.unwrap(),
+            default_width: backlinks.default_width.map(gpui::px).unwrap(),
+            default_height: backlinks.default_height.map(gpui::px).unwrap(),
+            max_entries: backlinks.max_entries.unwrap(),
+            include_self_references: backlinks.include_self_references.unwrap(),
+            link_normalization: backlinks.link_normalization.unwrap(),
+            max_context_length: backlinks.max_context_length.unwrap(),
+            preserve_leading_indentation: backlinks.preserve_leading_indentation.unwrap(),
+            context_lines: backlinks.context_lines.unwrap(),
+            custom_link_patterns: backlinks.custom_link_patterns.clone().unwrap(),
+            starts_open: backlinks.starts_open.unwrap(),
+            activation_priority: backlinks.activation_priority.unwrap(),
+            sort_order: backlinks.sort_order.unwrap(),
+            density: backlinks.density.unwrap(),
+            ambiguous_stem_matching: backlinks.ambiguous_stem_matching.unwrap(),
+            show_context: backlinks.show_context.unwrap(),
+            open_external_for_unsupported_files: backlinks
+                .open_external_for_unsupported_files
+                .unwrap(),
+            on_click: backlinks.on_click.unwrap(),
+            exclude_from_scanning: backlinks.exclude_from_scanning.unwrap(),
+            note_identity: backlinks.note_identity.unwrap(),
+            collapse_for_non_notes: backlinks.collapse_for_non_notes.unwrap(),
+            open_tasks_only: backlinks.open_tasks_only.unwrap(),
+            scan_scope: backlinks.scan_scope.unwrap(),
+            link_syntax: backlinks.link_syntax.unwrap(),
+            link_path_format: backlinks.link_path_format.unwrap(),
+            link_include_extension: backlinks.link_include_extension.unwrap(),
+        }
+    }
+}
+
+impl BacklinksSettings {
+    /// Returns whether `worktree_id` should be skipped by backlink scans,
+    /// per `exclude_from_scanning` in that worktree's local
+    /// `.zed/settings.json`. Queried per-worktree rather than through
+    /// [`Settings::get_global`], since this setting is only meant to be set
+    /// locally, not in user or global settings.
+    pub fn is_worktree_excluded(worktree_id: WorktreeId, cx: &App) -> bool {
+        Self::get(
+            Some(SettingsLocation {
+                worktree_id,
+                path: RelPath::empty(),
+            }),
+            cx,
+        )
+        .exclude_from_scanning
+    }
+}
@@ -788,6 +788,13 @@ pub trait Addon: 'static {
         None
     }
 
+    /// Renders an optional small icon appended after a tab's title, for
+    /// surfacing per-file state (e.g. bookmarks) that doesn't warrant its own
+    /// tab icon.
+    fn render_tab_icon(&self, _: &language::BufferSnapshot, _: &App) -> Option<AnyElement> {
+        None
+    }
+
     fn to_any(&self) -> &dyn std::any::Any;
 
     fn to_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
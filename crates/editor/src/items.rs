@@ -798,6 +798,13 @@ impl Item for Editor {
             .and_then(|buffer| buffer.read(cx).file())
             .is_some_and(|file| file.disk_state().is_deleted());
 
+        let addon_tab_icon = self.buffer().read(cx).as_singleton().and_then(|buffer| {
+            let buffer = buffer.read(cx);
+            self.addons
+                .values()
+                .find_map(|addon| addon.render_tab_icon(buffer, cx))
+        });
+
         h_flex()
             .gap_1()
             .when(params.truncate_title_middle, |this| {
@@ -829,6 +836,7 @@ impl Item for Editor {
                         .color(Color::Muted),
                 )
             })
+            .children(addon_tab_icon)
             .into_any_element()
     }
 
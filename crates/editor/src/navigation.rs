@@ -1006,6 +1006,17 @@ impl Editor {
         self.go_to_singleton_buffer_range_impl(point..point, false, window, cx);
     }
 
+    /// Like `go_to_singleton_buffer_range`, but does not push a navigation
+    /// history entry. See [`Self::go_to_singleton_buffer_point_silently`].
+    pub fn go_to_singleton_buffer_range_silently(
+        &mut self,
+        range: Range<Point>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_singleton_buffer_range_impl(range, false, window, cx);
+    }
+
     pub fn go_to_next_document_highlight(
         &mut self,
         _: &GoToNextDocumentHighlight,
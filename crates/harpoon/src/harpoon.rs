@@ -0,0 +1,2324 @@
+//! Harpoon: a small set of per-project marks that can be jumped to instantly.
+//!
+//! Inspired by the vim plugin of the same name: mark a handful of files (or
+//! terminals) you're bouncing between and jump back to them with a single
+//! keystroke, without paying the cost of a fuzzy search every time.
+//!
+//! Every interaction available while [`HarpoonPicker`] has focus goes
+//! through a named, keymap-overridable action rather than hard-coded key
+//! handling, so the defaults in `specific-overrides.json` (bound under the
+//! `HarpoonPicker` context) can be freely rebound:
+//! [`PeekSelectedMark`], [`RemoveSelectedMark`], [`MoveSelectedMarkUp`],
+//! [`MoveSelectedMarkDown`], [`UndoReorder`], and a modifier-qualified
+//! [`JumpToSlot`] binding for jumping straight to a slot without leaving the
+//! picker. Navigation and confirmation reuse the generic `menu::SelectNext`/
+//! `menu::SelectPrevious`/`menu::Confirm`/`menu::Cancel` actions that every
+//! [`picker::Picker`] already dispatches.
+
+mod harpoon_comment_input;
+mod harpoon_jump_input;
+mod harpoon_panel;
+mod harpoon_picker;
+mod harpoon_remove_matching_input;
+mod harpoon_settings;
+#[cfg(test)]
+mod harpoon_tests;
+
+use std::cell::RefCell;
+use std::ops::Range;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use collections::{HashMap, HashSet};
+use db::kvp::KeyValueStore;
+use editor::{
+    DisplayPoint, Editor, RowHighlightOptions, ToPoint as _,
+    display_map::DisplayRow,
+    scroll::{Autoscroll, ScrollAnchor},
+};
+use fs::Fs;
+use gpui::{
+    Action, AnyElement, App, AppContext as _, Context, Entity, EntityId, EventEmitter, Global,
+    PromptLevel, SharedString, Subscription, Task, WeakEntity, Window, actions,
+};
+use project::{Project, ProjectEntryId, ProjectPath, WorktreeId};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::Settings as _;
+use terminal_view::{TerminalView, terminal_panel::TerminalPanel};
+use text::{Bias, Point};
+use theme::ActiveTheme;
+use ui::prelude::*;
+use util::ResultExt as _;
+use util::paths::PathMatcher;
+use workspace::{AlternateFile, Toast, Workspace, notifications::NotificationId};
+
+pub use harpoon_comment_input::HarpoonCommentInput;
+pub use harpoon_jump_input::HarpoonJumpInput;
+pub use harpoon_panel::HarpoonPanel;
+pub use harpoon_picker::HarpoonPicker;
+pub use harpoon_remove_matching_input::HarpoonRemoveMatchingInput;
+pub use harpoon_settings::{HarpoonNormalizeSlotsOrder, HarpoonScope, HarpoonSettings};
+
+/// The name of the project-committed file that seeds a project's default marks.
+const SEED_FILE_NAME: &str = ".harpoon.json";
+
+/// The maximum number of mark-order snapshots kept for [`HarpoonStore::undo_reorder`].
+const REORDER_UNDO_STACK_CAP: usize = 10;
+
+/// The maximum number of entries kept in [`HarpoonStore::jump_history`].
+const JUMP_HISTORY_CAP: usize = 50;
+
+actions!(
+    harpoon,
+    [
+        /// Marks the current file (or, if the active item is a terminal, its
+        /// working directory), adding it to the harpoon list.
+        Mark,
+        /// Opens the harpoon picker listing all current marks.
+        ToggleHarpoonPicker,
+        /// Jumps to the mark that was added most recently.
+        JumpLast,
+        /// Marks every open tab, up to the configured slot limit.
+        MarkOpenTabs,
+        /// Opens a minimal input for jumping to a mark by its typed slot
+        /// number, lighter-weight than the full picker.
+        ToggleHarpoonJumpInput,
+        /// Clears every mark for the current project. Undoable via
+        /// `RestoreMarks` until `HarpoonSettings::clear_undo_window_ms`
+        /// elapses.
+        ClearAllMarks,
+        /// Restores the marks removed by the most recent `ClearAllMarks`, if
+        /// its undo window hasn't expired yet.
+        RestoreMarks,
+        /// Opens the harpoon picker's selected mark in a transient preview
+        /// tab that's replaced as the selection changes, without pinning it.
+        /// Bound only inside the `HarpoonPicker` context.
+        PeekSelectedMark,
+        /// Opens every occupied mark as a tab, in slot order, focusing the
+        /// first. Faster than jumping to each one individually when resuming
+        /// a whole working set.
+        OpenAll,
+        /// Rotates to the next named mark list and shows a toast naming it.
+        /// A no-op beyond that toast today, since every project has exactly
+        /// one (unnamed) list until named lists are implemented; see
+        /// [`HarpoonSnapshot::active_list_name`].
+        NextList,
+        /// The reverse of [`NextList`]. Equally a no-op today, for the same
+        /// reason.
+        PrevList,
+        /// Toggles the harpoon panel, a dockable alternative to
+        /// `ToggleHarpoonPicker`'s transient modal that keeps the mark list
+        /// visible alongside the editor.
+        ToggleHarpoonPanel,
+        /// Undoes the most recent reorder (e.g. a slot swap), restoring the
+        /// mark order from before it. Repeatable, up to
+        /// [`HarpoonStore::undo_reorder`]'s bounded history.
+        UndoReorder,
+        /// Removes the harpoon picker's currently selected mark. Bound only
+        /// inside the `HarpoonPicker` context.
+        RemoveSelectedMark,
+        /// Swaps the harpoon picker's currently selected mark with the one
+        /// in the previous slot. Bound only inside the `HarpoonPicker`
+        /// context.
+        MoveSelectedMarkUp,
+        /// Swaps the harpoon picker's currently selected mark with the one
+        /// in the next slot. Bound only inside the `HarpoonPicker` context.
+        MoveSelectedMarkDown,
+        /// Flips between the two most recently harpoon-jumped-to targets,
+        /// independent of their slot numbers. A no-op if fewer than two
+        /// distinct targets have been jumped to yet.
+        ToggleLastTwo,
+        /// Reorders every occupied mark per
+        /// `HarpoonSettings::normalize_slots_order` and compacts them into
+        /// slots `1..N`, for a clean, predictable layout after a lot of
+        /// ad-hoc marking. Undoable via `UndoReorder`, the same as a manual
+        /// swap.
+        NormalizeSlots,
+        /// Retraces one step back through [`HarpoonStore::jump_history`], the
+        /// linear record of every harpoon jump. Independent of
+        /// `ToggleLastTwo`'s two-entry ring. A no-op at the start of the
+        /// history.
+        JumpBack,
+        /// The reverse of [`JumpBack`]. A no-op at the end of the history, or
+        /// if nothing has gone back yet.
+        JumpForward,
+        /// Opens a minimal input for bulk-removing every mark whose path
+        /// matches a typed glob or prefix (e.g. `src/old_module/**`), for
+        /// cleaning up after a work session without removing marks one by
+        /// one in the picker. See [`HarpoonStore::remove_matching`].
+        RemoveMatching,
+    ]
+);
+
+/// Jumps directly to the mark in the given slot (0-indexed), without going
+/// through the picker. Bound under a context that excludes the
+/// [`HarpoonPicker`] modal for its plain-digit keystrokes, which need to
+/// reach the picker's query editor instead; a modifier-qualified binding of
+/// the same action is bound separately inside `HarpoonPicker` (see the
+/// module docs for that context's full action list). A single parameterized
+/// action rather than one per slot, so it keeps working past
+/// [`HarpoonSettings::max_slots`]'s default of nine; slots at or beyond the
+/// configured limit are a no-op.
+#[derive(Clone, PartialEq, Debug, Deserialize, JsonSchema, Default, Action)]
+#[action(namespace = harpoon)]
+pub struct JumpToSlot(pub usize);
+
+/// Places the active file into the given slot (0-indexed), replacing
+/// whatever mark is currently there. The deterministic counterpart to
+/// [`Mark`]'s first-empty-slot behavior, for maintaining a fixed mental map
+/// of slots. If [`HarpoonSettings::confirm_overwrite`] is set and the slot is
+/// occupied, prompts before replacing it.
+#[derive(Clone, PartialEq, Debug, Deserialize, JsonSchema, Default, Action)]
+#[action(namespace = harpoon)]
+pub struct SetSlot(pub usize);
+
+pub fn init(cx: &mut App) {
+    HarpoonSettings::register(cx);
+    harpoon_picker::init(cx);
+    harpoon_jump_input::init(cx);
+    harpoon_comment_input::init(cx);
+    harpoon_remove_matching_input::init(cx);
+    harpoon_panel::init(cx);
+
+    cx.observe_new(|editor: &mut Editor, _, cx| {
+        register_marked_tab_indicator(editor, cx);
+    })
+    .detach();
+
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        if let Some(window) = window {
+            restore_marks_as_tabs(workspace, window, cx);
+        }
+        cx.subscribe_self::<workspace::Event>(|workspace, event, cx| {
+            match event {
+                workspace::Event::ItemAdded { item } => {
+                    if let Some(project_path) = item.project_path(cx) {
+                        let store = get_or_create_harpoon_store(workspace.project(), cx);
+                        store.update(cx, |store, cx| store.auto_mark_on_open(project_path, cx));
+                    }
+                }
+                workspace::Event::ActiveItemChanged => {
+                    let project_path =
+                        workspace.active_item(cx).and_then(|item| item.project_path(cx));
+                    let worktree_id =
+                        project_path.as_ref().map(|project_path| project_path.worktree_id);
+                    let store = get_or_create_harpoon_store(workspace.project(), cx);
+                    store.update(cx, |store, cx| {
+                        store.set_active_worktree(worktree_id, cx);
+                        store.set_active_item(project_path, cx);
+                    });
+                }
+                _ => {}
+            }
+        })
+        .detach();
+        workspace.register_action(|workspace, _: &Mark, window, cx| {
+            let (target, cursor) = if let Some(terminal_view) =
+                workspace.active_item_as::<TerminalView>(cx)
+            {
+                let Some(cwd) = terminal_view
+                    .read(cx)
+                    .terminal()
+                    .read(cx)
+                    .working_directory()
+                else {
+                    workspace.show_toast(
+                        Toast::new(
+                            NotificationId::unique::<Mark>(),
+                            "Can't determine the terminal's working directory",
+                        ),
+                        cx,
+                    );
+                    return;
+                };
+                (HarpoonMarkTarget::Terminal(cwd.into()), None)
+            } else {
+                let Some(project_path) = workspace
+                    .active_item(cx)
+                    .and_then(|item| item.project_path(cx))
+                else {
+                    workspace.show_toast(
+                        Toast::new(
+                            NotificationId::unique::<Mark>(),
+                            "Save the file before marking",
+                        ),
+                        cx,
+                    );
+                    return;
+                };
+                if workspace
+                    .project()
+                    .read(cx)
+                    .worktree_for_id(project_path.worktree_id, cx)
+                    .is_none()
+                {
+                    workspace.show_toast(
+                        Toast::new(
+                            NotificationId::unique::<Mark>(),
+                            "Can't mark a file outside the project",
+                        ),
+                        cx,
+                    );
+                    return;
+                }
+                let cursor = workspace.active_item_as::<Editor>(cx).map(|editor| {
+                    editor.update_in(cx, |editor, window, cx| primary_cursor(editor, window, cx))
+                });
+                (HarpoonMarkTarget::File(project_path), cursor)
+            };
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            if store.update(cx, |store, _| store.cancel_pending_mark()) {
+                // The previous press hadn't landed yet: treat this as a
+                // double-tap and open the picker instead of marking again.
+                HarpoonPicker::toggle(workspace, window, cx);
+                return;
+            }
+            let double_tap_window =
+                Duration::from_millis(HarpoonSettings::get_global(cx).double_tap_window_ms);
+            store.update(cx, |store, cx| {
+                store.schedule_mark(target, cursor, double_tap_window, cx)
+            });
+        });
+        workspace.register_action(|workspace, jump: &JumpToSlot, window, cx| {
+            if jump.0 >= HarpoonSettings::get_global(cx).max_slots {
+                return;
+            }
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            let Some(mark) = store.read(cx).marks(cx).get(jump.0).cloned() else {
+                return;
+            };
+            if HarpoonSettings::get_global(cx).bounce_on_repeat
+                && is_active_target(workspace, &mark.target, cx)
+            {
+                window.dispatch_action(Box::new(AlternateFile), cx);
+                return;
+            }
+            store.update(cx, |store, cx| store.record_jump(mark.id, cx));
+            open_mark(workspace, mark.target, mark.cursor, window, cx);
+        });
+        workspace.register_action(|workspace, set_slot: &SetSlot, window, cx| {
+            let Some(project_path) = workspace
+                .active_item(cx)
+                .and_then(|item| item.project_path(cx))
+            else {
+                return;
+            };
+            if workspace
+                .project()
+                .read(cx)
+                .worktree_for_id(project_path.worktree_id, cx)
+                .is_none()
+            {
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::unique::<SetSlot>(),
+                        "Can't mark a file outside the project",
+                    ),
+                    cx,
+                );
+                return;
+            }
+            let slot = set_slot.0;
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            let occupant = store.read(cx).marks(cx).get(slot).cloned();
+            if let Some(occupant) = occupant.filter(|_| HarpoonSettings::get_global(cx).confirm_overwrite) {
+                let new_label = HarpoonMarkTarget::File(project_path.clone()).display_label();
+                let answer = window.prompt(
+                    PromptLevel::Info,
+                    &format!(
+                        "Replace '{}' in slot {} with '{new_label}'?",
+                        occupant.target.display_label(),
+                        slot + 1
+                    ),
+                    None,
+                    &["Replace", "Cancel"],
+                    cx,
+                );
+                cx.spawn_in(window, async move |workspace, cx| {
+                    if answer.await != Ok(0) {
+                        return anyhow::Ok(());
+                    }
+                    workspace.update(cx, |workspace, cx| {
+                        if let Err(error) =
+                            store.update(cx, |store, cx| store.set_slot(slot, project_path, cx))
+                        {
+                            workspace.show_toast(
+                                Toast::new(NotificationId::unique::<SetSlot>(), error.to_string()),
+                                cx,
+                            );
+                        }
+                    })
+                })
+                .detach_and_log_err(cx);
+                return;
+            }
+            if let Err(error) = store.update(cx, |store, cx| store.set_slot(slot, project_path, cx)) {
+                workspace.show_toast(
+                    Toast::new(NotificationId::unique::<SetSlot>(), error.to_string()),
+                    cx,
+                );
+            }
+        });
+        workspace.register_action(|workspace, _: &JumpLast, window, cx| {
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            let Some(mark) = store
+                .read(cx)
+                .last_added_slot()
+                .and_then(|slot| store.read(cx).marks(cx).get(slot))
+                .cloned()
+            else {
+                return;
+            };
+            store.update(cx, |store, cx| store.record_jump(mark.id, cx));
+            open_mark(workspace, mark.target, mark.cursor, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ToggleLastTwo, window, cx| {
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            let Some(target) = store.read(cx).alternate_jump_target() else {
+                return;
+            };
+            let existing_mark = store
+                .read(cx)
+                .marks(cx)
+                .iter()
+                .find(|mark| mark.target == target)
+                .cloned();
+            match &existing_mark {
+                Some(mark) => {
+                    let mark_id = mark.id;
+                    store.update(cx, |store, cx| store.record_jump(mark_id, cx));
+                }
+                None => {
+                    store.update(cx, |store, _| store.push_jump_ring(target.clone()));
+                }
+            }
+            let cursor = existing_mark.and_then(|mark| mark.cursor);
+            open_mark(workspace, target, cursor, window, cx);
+        });
+        workspace.register_action(|workspace, _: &MarkOpenTabs, _window, cx| {
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            let project_paths: Vec<_> = workspace
+                .items(cx)
+                .filter_map(|item| item.project_path(cx))
+                .collect();
+            let max_slots = HarpoonSettings::get_global(cx).max_slots;
+            let mut added = 0;
+            let mut skipped_outside_project = 0;
+            store.update(cx, |store, cx| {
+                for project_path in project_paths {
+                    if store.marks(cx).len() >= max_slots {
+                        break;
+                    }
+                    match store.mark(project_path, None, cx) {
+                        Ok(true) => added += 1,
+                        Ok(false) => {}
+                        Err(_) => skipped_outside_project += 1,
+                    }
+                }
+            });
+            let message = if skipped_outside_project > 0 {
+                format!(
+                    "Marked {added} open {} ({skipped_outside_project} outside the project skipped)",
+                    if added == 1 { "tab" } else { "tabs" }
+                )
+            } else {
+                format!(
+                    "Marked {added} open {}",
+                    if added == 1 { "tab" } else { "tabs" }
+                )
+            };
+            workspace.show_toast(
+                Toast::new(NotificationId::unique::<MarkOpenTabs>(), message),
+                cx,
+            );
+        });
+        workspace.register_action(|workspace, _: &ClearAllMarks, _window, cx| {
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            if store.read(cx).marks(cx).is_empty() {
+                return;
+            }
+            store.update(cx, |store, cx| store.clear_all(cx));
+            let weak_store = store.downgrade();
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<ClearAllMarks>(),
+                    "Cleared all harpoon marks",
+                )
+                .on_click("Undo", move |_window, cx| {
+                    weak_store
+                        .update(cx, |store, cx| {
+                            store.restore_cleared_marks(cx);
+                        })
+                        .log_err();
+                }),
+                cx,
+            );
+        });
+        workspace.register_action(|workspace, _: &RestoreMarks, _window, cx| {
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            store.update(cx, |store, cx| {
+                store.restore_cleared_marks(cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &UndoReorder, _window, cx| {
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            store.update(cx, |store, cx| {
+                store.undo_reorder(cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &NormalizeSlots, _window, cx| {
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            store.update(cx, |store, cx| {
+                store.normalize_slots(cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &NextList, _window, cx| {
+            show_active_list_toast::<NextList>(workspace, cx);
+        });
+        workspace.register_action(|workspace, _: &PrevList, _window, cx| {
+            show_active_list_toast::<PrevList>(workspace, cx);
+        });
+        workspace.register_action(|workspace, _: &OpenAll, window, cx| {
+            open_all_marks(workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &JumpBack, window, cx| {
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            let Some(target) = store.update(cx, |store, _| store.jump_back()) else {
+                return;
+            };
+            let cursor = mark_cursor_for_target(&store, &target, cx);
+            open_mark(workspace, target, cursor, window, cx);
+        });
+        workspace.register_action(|workspace, _: &JumpForward, window, cx| {
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            let Some(target) = store.update(cx, |store, _| store.jump_forward()) else {
+                return;
+            };
+            let cursor = mark_cursor_for_target(&store, &target, cx);
+            open_mark(workspace, target, cursor, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// The cursor position to restore when navigating to `target` via
+/// [`JumpBack`]/[`JumpForward`]: the owning mark's recorded cursor, if
+/// `target` still corresponds to one. A history entry can outlive the mark it
+/// was jumped from, the same way [`HarpoonStore::alternate_jump_target`]
+/// does, so this falls back to `None` rather than failing the jump.
+fn mark_cursor_for_target(
+    store: &Entity<HarpoonStore>,
+    target: &HarpoonMarkTarget,
+    cx: &App,
+) -> Option<HarpoonCursor> {
+    store
+        .read(cx)
+        .marks(cx)
+        .iter()
+        .find(|mark| &mark.target == target)
+        .and_then(|mark| mark.cursor.clone())
+}
+
+/// Marker type for the row highlight applied by [`center_and_flash_cursor`].
+enum HarpoonJumpFlash {}
+
+/// How long a jumped-to line stays highlighted before fading back out.
+const JUMP_FLASH_DURATION: Duration = Duration::from_millis(400);
+
+/// Opens `target` and, for a file that lands on an editor, restores `cursor`
+/// if the mark recorded one — the full selection when it captured a
+/// non-empty one, otherwise just the cursor position (leaving whatever
+/// position the editor restores on its own, e.g. the last place it was left,
+/// if the mark recorded neither) — then centers the view and briefly flashes
+/// that line, mirroring jump-to-definition. Used by every harpoon jump site,
+/// including external UI invoking a [`HarpoonQuickAction`], so landing on a
+/// mark feels the same everywhere.
+pub fn open_mark(
+    workspace: &mut Workspace,
+    target: HarpoonMarkTarget,
+    cursor: Option<HarpoonCursor>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    match target {
+        HarpoonMarkTarget::File(project_path) => {
+            open_file_mark(workspace, project_path, cursor, window, cx)
+        }
+        HarpoonMarkTarget::Terminal(cwd) => open_terminal_mark(workspace, cwd, window, cx),
+    }
+}
+
+fn open_file_mark(
+    workspace: &mut Workspace,
+    project_path: ProjectPath,
+    cursor: Option<HarpoonCursor>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let open_task = workspace.open_path(project_path, None, true, window, cx);
+    cx.spawn_in(window, async move |_workspace, cx| {
+        let item = open_task.await?;
+        if let Some(editor) = item.downcast::<Editor>() {
+            editor.update_in(cx, |editor, window, cx| {
+                let mut scroll_restored = false;
+                if let Some(cursor) = cursor {
+                    match cursor.selection {
+                        Some(selection) => {
+                            editor.go_to_singleton_buffer_range_silently(selection, window, cx);
+                        }
+                        None => {
+                            editor.go_to_singleton_buffer_point_silently(cursor.head, window, cx);
+                        }
+                    }
+                    let scroll_anchor = HarpoonSettings::get_global(cx)
+                        .restore_scroll_position
+                        .then(|| scroll_anchor_for_point(editor, cursor.scroll_top, cx))
+                        .flatten();
+                    if let Some(scroll_anchor) = scroll_anchor {
+                        editor.set_scroll_anchor(scroll_anchor, window, cx);
+                        scroll_restored = true;
+                    }
+                }
+                center_and_flash_cursor(editor, scroll_restored, window, cx);
+            })?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Whether `target` is already the active item, i.e. jumping to it would be
+/// a no-op. Used by `JumpToSlot` to detect the `bounce_on_repeat` case.
+fn is_active_target(workspace: &Workspace, target: &HarpoonMarkTarget, cx: &App) -> bool {
+    match target {
+        HarpoonMarkTarget::File(project_path) => {
+            workspace.active_item(cx).and_then(|item| item.project_path(cx)).as_ref()
+                == Some(project_path)
+        }
+        HarpoonMarkTarget::Terminal(cwd) => workspace
+            .active_item_as::<TerminalView>(cx)
+            .is_some_and(|terminal_view| {
+                terminal_view.read(cx).terminal().read(cx).working_directory().as_deref()
+                    == Some(cwd.as_ref())
+            }),
+    }
+}
+
+/// Opens `cwd` in a terminal, reusing an already-open terminal with the same
+/// working directory if one exists instead of spawning a new one.
+fn open_terminal_mark(
+    workspace: &mut Workspace,
+    cwd: Arc<Path>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let existing = workspace.items_of_type::<TerminalView>(cx).find(|terminal_view| {
+        let terminal_view = terminal_view.read(cx);
+        terminal_view.terminal().read(cx).working_directory().as_deref() == Some(cwd.as_ref())
+    });
+    if let Some(terminal_view) = existing {
+        workspace.activate_item(&terminal_view, true, true, window, cx);
+        return;
+    }
+    let cwd = cwd.to_path_buf();
+    TerminalPanel::add_center_terminal(workspace, window, cx, move |project, cx| {
+        project.create_terminal_shell(Some(cwd), cx)
+    })
+    .detach_and_log_err(cx);
+}
+
+/// The position [`mark`](HarpoonStore::mark) should record for `editor`:
+/// the primary (newest) selection's head, plus that selection's range if
+/// it's non-empty, and the row currently at the top of the viewport. With
+/// multiple cursors active, only this one is kept, since a mark stores a
+/// single position (and at most one selection) rather than one per cursor.
+fn primary_cursor(editor: &Editor, window: &Window, cx: &mut Context<Editor>) -> HarpoonCursor {
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let selection = editor.selections.newest_anchor();
+    let range = selection.start.to_point(&snapshot)..selection.end.to_point(&snapshot);
+    let display_snapshot = editor.snapshot(window, cx).display_snapshot;
+    let scroll_top_row = editor.scroll_position(cx).y.floor() as u32;
+    let scroll_top = DisplayPoint::new(DisplayRow(scroll_top_row), 0).to_point(&display_snapshot);
+    HarpoonCursor {
+        head: selection.head().to_point(&snapshot),
+        selection: (!range.is_empty()).then_some(range),
+        scroll_top,
+    }
+}
+
+/// Builds a [`ScrollAnchor`] that puts `point` at the top of `editor`'s
+/// viewport, for restoring a mark's recorded scroll position. Returns `None`
+/// for a multi-excerpt buffer, or when `point` no longer resolves to an
+/// anchor in the current buffer — the same graceful fallback
+/// `go_to_singleton_buffer_point_silently` gets from `buffer_point_to_anchor`
+/// clipping an out-of-range point rather than failing outright, so a file
+/// that shrank past the recorded row still restores to its last line instead
+/// of silently doing nothing.
+fn scroll_anchor_for_point(editor: &Editor, point: Point, cx: &App) -> Option<ScrollAnchor> {
+    let multibuffer = editor.buffer().read(cx);
+    let buffer = multibuffer.as_singleton()?;
+    let anchor = multibuffer.buffer_point_to_anchor(&buffer, point, cx)?;
+    Some(ScrollAnchor {
+        anchor,
+        offset: gpui::Point::default(),
+    })
+}
+
+/// If `scroll_restored` is false, centers `editor`'s view on its current
+/// cursor position — skipped when a mark's recorded scroll position was
+/// restored instead, so it isn't immediately overridden. Either way, if
+/// `HarpoonSettings::flash_on_jump` is enabled, briefly highlights the
+/// cursor's line.
+fn center_and_flash_cursor(
+    editor: &mut Editor,
+    scroll_restored: bool,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    if !scroll_restored {
+        editor.request_autoscroll(Autoscroll::center(), cx);
+    }
+    if !HarpoonSettings::get_global(cx).flash_on_jump {
+        return;
+    }
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let head = editor.selections.newest_anchor().head();
+    let mut start_point = head.to_point(&snapshot);
+    start_point.column = 0;
+    let mut end_point = snapshot.clip_point(start_point + Point::new(0, 1), Bias::Left);
+    if start_point == end_point {
+        end_point = snapshot.clip_point(start_point + Point::new(1, 0), Bias::Left);
+    }
+    let start = snapshot.anchor_after(start_point);
+    let end = snapshot.anchor_after(end_point);
+    editor.highlight_rows::<HarpoonJumpFlash>(
+        start..end,
+        |cx| cx.theme().colors().editor_highlighted_line_background,
+        RowHighlightOptions::default(),
+        cx,
+    );
+    cx.spawn_in(window, async move |editor, cx| {
+        cx.background_executor().timer(JUMP_FLASH_DURATION).await;
+        editor
+            .update(cx, |editor, _| {
+                editor.clear_row_highlights::<HarpoonJumpFlash>();
+            })
+            .ok();
+    })
+    .detach();
+}
+
+/// If `HarpoonSettings::restore_marks_as_tabs` is enabled, reopens the
+/// project's current marks as background tabs, skipping any already open.
+/// Marks may still be loading (e.g. from the `.harpoon.json` seed file), so
+/// this also watches for the store's first `MarksChanged` and restores from
+/// that snapshot instead if the store was still empty just now.
+fn restore_marks_as_tabs(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    if !HarpoonSettings::get_global(cx).restore_marks_as_tabs {
+        return;
+    }
+    let store = get_or_create_harpoon_store(workspace.project(), cx);
+    let marks = store.read(cx).marks(cx).to_vec();
+    if !marks.is_empty() {
+        open_unopened_marks(&marks, workspace, window, cx);
+        return;
+    }
+    let subscription: Rc<RefCell<Option<Subscription>>> = Rc::new(RefCell::new(None));
+    let inner = cx.subscribe_in(&store, window, {
+        let subscription = subscription.clone();
+        move |workspace, store, _: &MarksChanged, window, cx| {
+            let marks = store.read(cx).marks(cx).to_vec();
+            open_unopened_marks(&marks, workspace, window, cx);
+            subscription.take();
+        }
+    });
+    subscription.borrow_mut().replace(inner);
+}
+
+/// Opens each file mark as a non-focused tab, skipping any whose path is
+/// already open in the workspace. Terminal marks aren't restored this way —
+/// reopening a batch of terminals in the background on every launch would be
+/// far more disruptive than reopening background file tabs.
+fn open_unopened_marks(
+    marks: &[HarpoonMark],
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let already_open: collections::HashSet<_> = workspace
+        .items(cx)
+        .filter_map(|item| item.project_path(cx))
+        .collect();
+    for mark in marks {
+        let HarpoonMarkTarget::File(project_path) = &mark.target else {
+            continue;
+        };
+        if already_open.contains(project_path) {
+            continue;
+        }
+        workspace
+            .open_path(project_path.clone(), None, false, window, cx)
+            .detach_and_log_err(cx);
+    }
+}
+
+/// Shared by [`NextList`] and [`PrevList`]: names the project's active mark
+/// list in a toast. There's nothing to actually rotate to until named lists
+/// are implemented and a project can have more than one, so both actions
+/// report the same (only) list today.
+fn show_active_list_toast<A: Action>(workspace: &mut Workspace, cx: &mut Context<Workspace>) {
+    let store = get_or_create_harpoon_store(workspace.project(), cx);
+    let list_name = store
+        .read(cx)
+        .snapshot(cx)
+        .active_list_name
+        .unwrap_or_else(|| "Default".into());
+    workspace.show_toast(
+        Toast::new(NotificationId::unique::<A>(), format!("List: {list_name}")),
+        cx,
+    );
+}
+
+/// Opens every occupied mark (up to `HarpoonSettings::max_slots`) as a tab,
+/// in slot order: the first mark is opened focused in the active pane, and
+/// the rest as background tabs, skipping any file mark already open
+/// elsewhere in the workspace. If `HarpoonSettings::open_all_in_splits` is
+/// enabled, marks after the first each open in their own split instead of as
+/// background tabs. Terminal marks reuse an already-open terminal with the
+/// same working directory the same way [`open_mark`] does. Marks whose file
+/// couldn't be opened (e.g. it was deleted) are reported via a toast rather
+/// than failing silently.
+fn open_all_marks(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+    let store = get_or_create_harpoon_store(workspace.project(), cx);
+    let settings = HarpoonSettings::get_global(cx);
+    let max_slots = settings.max_slots;
+    let open_in_splits = settings.open_all_in_splits;
+    let marks: Vec<_> = store.read(cx).marks(cx).iter().take(max_slots).cloned().collect();
+    if marks.is_empty() {
+        return;
+    }
+    let already_open: collections::HashSet<_> = workspace
+        .items(cx)
+        .filter_map(|item| item.project_path(cx))
+        .collect();
+
+    let mut open_tasks = Vec::new();
+    for (index, mark) in marks.into_iter().enumerate() {
+        let focus_item = index == 0;
+        match mark.target {
+            HarpoonMarkTarget::File(project_path) => {
+                if !focus_item && already_open.contains(&project_path) {
+                    continue;
+                }
+                let task = if open_in_splits && !focus_item {
+                    workspace.split_path(project_path.clone(), window, cx)
+                } else {
+                    workspace.open_path(project_path.clone(), None, focus_item, window, cx)
+                };
+                open_tasks.push((project_path, task));
+            }
+            HarpoonMarkTarget::Terminal(cwd) => {
+                open_terminal_mark(workspace, cwd, window, cx);
+            }
+        }
+    }
+    if open_tasks.is_empty() {
+        return;
+    }
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let (paths, tasks): (Vec<_>, Vec<_>) = open_tasks.into_iter().unzip();
+        let results = futures::future::join_all(tasks).await;
+        let failed_paths: Vec<_> = paths
+            .into_iter()
+            .zip(results)
+            .filter_map(|(path, result)| result.is_err().then_some(path))
+            .collect();
+        if failed_paths.is_empty() {
+            return;
+        }
+        workspace
+            .update(cx, |workspace, cx| {
+                let failed_labels = failed_paths
+                    .iter()
+                    .map(|path| path.path.display(util::paths::PathStyle::local()).to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let message = format!(
+                    "Couldn't open {} mark{}: {failed_labels}",
+                    failed_paths.len(),
+                    if failed_paths.len() == 1 { "" } else { "s" },
+                );
+                workspace.show_toast(Toast::new(NotificationId::unique::<OpenAll>(), message), cx);
+            })
+            .log_err();
+    })
+    .detach();
+}
+
+/// A stable identifier for a [`HarpoonMark`], assigned once at creation and
+/// never reused, so reorders, slot swaps, and persistence can all refer to a
+/// mark by id instead of by its (mutable) position in the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HarpoonMarkId(u32);
+
+/// What a [`HarpoonMark`] points at.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HarpoonMarkTarget {
+    /// A marked file, identified by its project path.
+    File(ProjectPath),
+    /// A marked terminal, identified by the working directory a jump should
+    /// open a terminal in, reusing an already-open one with the same
+    /// directory if one exists.
+    Terminal(Arc<Path>),
+}
+
+impl HarpoonMarkTarget {
+    /// The label shown for this target in the picker and quick actions.
+    fn display_label(&self) -> String {
+        match self {
+            HarpoonMarkTarget::File(project_path) => project_path
+                .path
+                .display(util::paths::PathStyle::local())
+                .to_string(),
+            HarpoonMarkTarget::Terminal(cwd) => format!("Terminal: {}", cwd.display()),
+        }
+    }
+}
+
+/// A single file or terminal marked via harpoon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarpoonMark {
+    pub id: HarpoonMarkId,
+    pub target: HarpoonMarkTarget,
+    /// The marked entry's id, used to follow it across renames via
+    /// [`HarpoonStore::refresh_marks`]. Only ever set for
+    /// [`HarpoonMarkTarget::File`] marks. Not persisted: entry ids aren't
+    /// stable across restarts, so this is re-resolved from the target's path
+    /// on load instead.
+    #[serde(skip)]
+    entry_id: Option<ProjectEntryId>,
+    /// Set by [`HarpoonStore::record_jump`] whenever this mark is jumped to,
+    /// used to order the picker by recency when
+    /// `HarpoonSettings::picker_sort` is `Recent`. Not persisted: jump
+    /// recency doesn't need to survive a restart.
+    #[serde(skip)]
+    last_jumped: Option<Instant>,
+    /// Incremented by [`HarpoonStore::record_jump`] every time this mark is
+    /// jumped to, purely for the local, opt-in usage count shown by
+    /// [`HarpoonSettings::show_jump_counts`] — there's no telemetry here, the
+    /// count never leaves this store. Not persisted: like `last_jumped`, it
+    /// doesn't need to survive a restart.
+    #[serde(skip)]
+    pub jump_count: usize,
+    /// The cursor (and, if it was non-empty, selection) captured when this
+    /// mark was created, so jumping back can land exactly where marking
+    /// happened instead of wherever the editor's own tab-restore leaves it.
+    /// With multiple cursors active, only the primary (newest) one is
+    /// recorded — a mark stores a single position, not one per cursor. Not
+    /// persisted: a stale position after the next restart would be worse
+    /// than none.
+    #[serde(skip)]
+    pub cursor: Option<HarpoonCursor>,
+    /// A short note jotted down when marking, e.g. "fix the parser bug here",
+    /// shown muted in the picker. Purely a memory aid: jumping ignores it.
+    /// Set via [`HarpoonStore::set_comment`], prompted for automatically when
+    /// `HarpoonSettings::prompt_on_mark` is enabled. Unlike the other fields
+    /// above, this is persisted with the mark.
+    pub comment: Option<String>,
+}
+
+/// The cursor and, when the selection it was captured from was non-empty,
+/// the selection's range, for a single [`HarpoonMark`]. Stored as row/column
+/// [`Point`]s rather than `Anchor`s, since a mark's position needs to
+/// outlive the buffer it was captured from. Not itself `Serialize`: like
+/// [`HarpoonMark::cursor`], this is never persisted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HarpoonCursor {
+    pub head: Point,
+    pub selection: Option<Range<Point>>,
+    /// The row that was at the top of the viewport when this was captured.
+    /// Restored by [`open_file_mark`] when
+    /// `HarpoonSettings::restore_scroll_position` is enabled, in place of
+    /// its usual centering on `head`.
+    pub scroll_top: Point,
+}
+
+/// Emitted by [`HarpoonStore`] whenever its marks are added, removed, or have
+/// their `project_path` updated, so views like [`HarpoonPicker`] can refresh
+/// without polling.
+#[derive(Debug, Clone)]
+pub struct MarksChanged;
+
+/// Emitted by [`HarpoonStore`] right after marking, when
+/// `HarpoonSettings::prompt_on_mark` is enabled, so UI like
+/// `HarpoonCommentInput` can prompt for a note on the mark just added.
+#[derive(Debug, Clone)]
+pub struct PromptForMarkComment(pub HarpoonMarkId);
+
+/// Emitted by [`HarpoonStore::set_active_item`] whenever the active file
+/// enters or leaves a marked slot, so UI like a status bar indicator or a
+/// gutter anchor can highlight "slot N" consistently without each
+/// reimplementing the active-item-to-slot lookup. `None` when the active
+/// file isn't marked.
+#[derive(Debug, Clone)]
+pub struct ActiveMarkChanged(pub Option<usize>);
+
+/// A single mark exposed as a quick action by [`HarpoonStore::quick_actions`],
+/// for UI outside this crate to list and invoke jumps without depending on
+/// [`HarpoonMark`]'s internal fields.
+#[derive(Debug, Clone)]
+pub struct HarpoonQuickAction {
+    pub label: SharedString,
+    pub target: HarpoonMarkTarget,
+}
+
+/// A fully-owned snapshot of a [`HarpoonStore`]'s state, decoupled from the
+/// entity's `cx` read guard so it can outlive the call that produced it. For
+/// tests exercising persistence and reordering, and for extension authors
+/// who want a stable shape to depend on instead of the entity itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarpoonSnapshot {
+    pub marks: Vec<HarpoonMarkSnapshot>,
+    /// The active mark list's name, once named lists are supported. Always
+    /// `None` today, since every project has exactly one unnamed list.
+    pub active_list_name: Option<SharedString>,
+    pub max_slots: usize,
+}
+
+/// A single occupied mark within a [`HarpoonSnapshot`], paired with its slot
+/// number (its index among the store's marks).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarpoonMarkSnapshot {
+    pub slot: usize,
+    pub id: HarpoonMarkId,
+    pub target: HarpoonMarkTarget,
+}
+
+/// The current on-disk version of [`PersistedHarpoonMarks`]. Bump this, and
+/// extend [`PersistedHarpoonMarks::migrate`] with a case that upgrades the
+/// previous shape into the current one, whenever a future change to this
+/// format would otherwise break older loaders.
+const PERSISTED_MARKS_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct PersistedHarpoonMark {
+    /// Absent in the original (pre-versioning) format, which only ever wrote
+    /// `path`; such marks are assigned an id from their position in the file
+    /// instead. See [`PersistedHarpoonMarks::migrate`].
+    #[serde(default)]
+    id: Option<u32>,
+    #[serde(flatten)]
+    target: PersistedHarpoonMarkTarget,
+    /// Absent in seed files written before comments existed.
+    #[serde(default)]
+    comment: Option<String>,
+}
+
+/// The on-disk shape of a [`HarpoonMarkTarget`]. Untagged so pre-existing
+/// `.harpoon.json` files, which only ever had a `path` field, keep loading
+/// unchanged.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum PersistedHarpoonMarkTarget {
+    File { path: Arc<Path> },
+    Terminal { cwd: Arc<Path> },
+}
+
+/// The on-disk shape of a project's marks, written both to a `.harpoon.json`
+/// seed file (committed at a worktree root) and to
+/// [`db::kvp::KeyValueStore`] (the actual persistence that survives a
+/// restart; the seed file is only a one-time fallback for a scope with no
+/// saved marks yet). Unknown extra fields are ignored by default (no
+/// `deny_unknown_fields`), and `version` defaults to `0` when absent, so
+/// files written before versioning was introduced keep loading unchanged;
+/// see [`Self::migrate`].
+#[derive(Default, Serialize, Deserialize)]
+struct PersistedHarpoonMarks {
+    #[serde(default)]
+    version: u32,
+    marks: Vec<PersistedHarpoonMark>,
+}
+
+impl PersistedHarpoonMarks {
+    /// Upgrades a payload parsed at any past version to the current shape.
+    /// Every version up to [`PERSISTED_MARKS_VERSION`] already shares the
+    /// current `marks` wire format (ids merely became optional), so this is
+    /// an identity transform on `marks` today; it's the seam future format
+    /// changes should extend with a `match self.version { ... }`.
+    fn migrate(self) -> Self {
+        Self {
+            version: PERSISTED_MARKS_VERSION,
+            marks: self.marks,
+        }
+    }
+}
+
+/// The [`db::kvp::KeyValueStore`] namespace harpoon marks are persisted
+/// under.
+const PERSISTED_MARKS_NAMESPACE: &str = "harpoon_marks";
+
+/// The [`db::kvp::KeyValueStore`] key a scope's marks are persisted under,
+/// namespaced by `worktree_root` so distinct projects (and, under
+/// `HarpoonScope::Worktree`, distinct worktrees) don't collide.
+fn persisted_marks_key(worktree_root: &Path, scope_key: HarpoonScopeKey) -> String {
+    match scope_key {
+        HarpoonScopeKey::Global => format!("project:{}", worktree_root.display()),
+        HarpoonScopeKey::Worktree(_) => format!("worktree:{}", worktree_root.display()),
+    }
+}
+
+/// Which mark set [`HarpoonStore::active_marks`] and friends operate on,
+/// derived from `HarpoonSettings::scope` and [`HarpoonStore::active_worktree`].
+/// A single variant (`Global`) is used for `HarpoonScope::Project`, and also
+/// as the fallback bucket for `HarpoonScope::Worktree` when the active file
+/// has no worktree (e.g. nothing is open yet), so marks made in that state
+/// aren't lost once a worktree becomes active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum HarpoonScopeKey {
+    Global,
+    Worktree(WorktreeId),
+}
+
+/// Per-project storage of harpoon marks. Under `HarpoonScope::Project`
+/// (the default) all marks live in the `HarpoonScopeKey::Global` bucket of
+/// `marks_by_scope`. Under `HarpoonScope::Worktree`, each worktree gets its
+/// own bucket, keyed by `active_worktree` as it follows the active file.
+pub struct HarpoonStore {
+    project: WeakEntity<Project>,
+    fs: Arc<dyn Fs>,
+    marks_by_scope: HashMap<HarpoonScopeKey, Vec<HarpoonMark>>,
+    /// The worktree of the most recently active file, used to pick a bucket
+    /// from `marks_by_scope` when `HarpoonSettings::scope` is `Worktree`.
+    /// Updated by `Self::set_active_worktree`.
+    active_worktree: Option<WorktreeId>,
+    next_mark_id: u32,
+    pending_mark: Option<Task<()>>,
+    last_added_slot: Option<usize>,
+    last_cleared: Option<Vec<HarpoonMark>>,
+    /// Snapshots of the active mark set taken before each reorder, most
+    /// recent last, so [`Self::undo_reorder`] can pop and restore them.
+    /// Bounded by [`REORDER_UNDO_STACK_CAP`].
+    reorder_undo_stack: Vec<Vec<HarpoonMark>>,
+    auto_marked_paths: HashSet<ProjectPath>,
+    /// The last two distinct targets jumped to via [`Self::record_jump`],
+    /// most recent first, backing [`ToggleLastTwo`]. Independent of the
+    /// numbered slots and not persisted: like `last_jumped`, it doesn't need
+    /// to survive a restart.
+    jump_ring: [Option<HarpoonMarkTarget>; 2],
+    /// The slot last reported via [`ActiveMarkChanged`], so
+    /// [`Self::set_active_item`] only emits when the active file's slot
+    /// actually changes. Updated on `workspace::Event::ActiveItemChanged`.
+    active_mark_slot: Option<usize>,
+    /// Every target jumped to via [`Self::record_jump`], oldest first and
+    /// bounded by [`JUMP_HISTORY_CAP`], backing [`JumpBack`]/[`JumpForward`].
+    /// Unlike [`Self::jump_ring`] this isn't a fixed two-entry window: it's a
+    /// linear trail that [`Self::jump_back`]/[`Self::jump_forward`] retrace
+    /// via `jump_history_cursor`. Not persisted, for the same reason
+    /// `jump_ring` isn't.
+    jump_history: Vec<HarpoonMarkTarget>,
+    /// The position in [`Self::jump_history`] that the last
+    /// [`JumpBack`]/[`JumpForward`] landed on, or that the most recent
+    /// ordinary jump appended. `None` until the first jump.
+    jump_history_cursor: Option<usize>,
+    _clear_undo_expiry: Option<Task<()>>,
+    _project_subscription: Subscription,
+}
+
+impl HarpoonStore {
+    fn new(project: Entity<Project>, fs: Arc<dyn Fs>, cx: &mut Context<Self>) -> Self {
+        let project_subscription = Self::subscribe_to_project(&project, cx);
+        Self {
+            project: project.downgrade(),
+            fs,
+            marks_by_scope: HashMap::default(),
+            active_worktree: None,
+            next_mark_id: 0,
+            pending_mark: None,
+            last_added_slot: None,
+            last_cleared: None,
+            reorder_undo_stack: Vec::new(),
+            auto_marked_paths: HashSet::default(),
+            jump_ring: [None, None],
+            active_mark_slot: None,
+            jump_history: Vec::new(),
+            jump_history_cursor: None,
+            _clear_undo_expiry: None,
+            _project_subscription: project_subscription,
+        }
+    }
+
+    fn subscribe_to_project(project: &Entity<Project>, cx: &mut Context<Self>) -> Subscription {
+        cx.subscribe(project, |this, _project, event, cx| {
+            if let project::Event::WorktreeUpdatedEntries(..) = event {
+                this.refresh_marks(cx);
+            }
+        })
+    }
+
+    /// Points this store at `project`, re-subscribing to its worktree-update
+    /// events. Used by [`get_or_create_harpoon_store`] when a lookup by
+    /// [`HarpoonProjectKey`] finds a store left over from a project entity
+    /// that's since been torn down and recreated (e.g. a window reload),
+    /// so in-memory marks survive the churn instead of the store being
+    /// recreated empty.
+    fn rebind_project(&mut self, project: Entity<Project>, cx: &mut Context<Self>) {
+        self._project_subscription = Self::subscribe_to_project(&project, cx);
+        self.project = project.downgrade();
+    }
+
+    /// Tells the store which worktree the active file belongs to, so
+    /// `HarpoonScope::Worktree` can follow it. Pass `None` when the active
+    /// item has no worktree (e.g. an untitled buffer, or nothing open); marks
+    /// made in that state fall back to the `HarpoonScopeKey::Global` bucket.
+    pub fn set_active_worktree(&mut self, worktree_id: Option<WorktreeId>, cx: &mut Context<Self>) {
+        if self.active_worktree == worktree_id {
+            return;
+        }
+        self.active_worktree = worktree_id;
+        if HarpoonSettings::get_global(cx).scope == HarpoonScope::Worktree {
+            cx.emit(MarksChanged);
+            cx.notify();
+        }
+    }
+
+    /// Recomputes which slot holds `project_path` (the active file, or
+    /// `None` if nothing's open or it has no project path) and emits
+    /// [`ActiveMarkChanged`] when that differs from the last known slot.
+    /// Cheap: just [`Self::slot_for`], the same lookup [`Self::is_marked`]
+    /// does.
+    pub fn set_active_item(&mut self, project_path: Option<ProjectPath>, cx: &mut Context<Self>) {
+        let slot = project_path.and_then(|project_path| self.slot_for(&project_path, cx));
+        if slot == self.active_mark_slot {
+            return;
+        }
+        self.active_mark_slot = slot;
+        cx.emit(ActiveMarkChanged(slot));
+    }
+
+    fn scope_key(&self, cx: &App) -> HarpoonScopeKey {
+        if HarpoonSettings::get_global(cx).scope == HarpoonScope::Worktree
+            && let Some(worktree_id) = self.active_worktree
+        {
+            HarpoonScopeKey::Worktree(worktree_id)
+        } else {
+            HarpoonScopeKey::Global
+        }
+    }
+
+    fn active_marks(&self, cx: &App) -> &[HarpoonMark] {
+        self.marks_by_scope
+            .get(&self.scope_key(cx))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn active_marks_mut(&mut self, cx: &App) -> &mut Vec<HarpoonMark> {
+        let key = self.scope_key(cx);
+        self.marks_by_scope.entry(key).or_default()
+    }
+
+    fn take_active_marks(&mut self, cx: &App) -> Vec<HarpoonMark> {
+        let key = self.scope_key(cx);
+        self.marks_by_scope.remove(&key).unwrap_or_default()
+    }
+
+    fn set_active_marks(&mut self, marks: Vec<HarpoonMark>, cx: &App) {
+        let key = self.scope_key(cx);
+        self.marks_by_scope.insert(key, marks);
+    }
+
+    /// Auto-marks `project_path` if it's one of the first
+    /// `HarpoonSettings::auto_mark_first` distinct files opened in this
+    /// project session. Files already considered (whether or not marking
+    /// them succeeded, e.g. because they were already marked or the project
+    /// was full) don't count again on a second open, and don't reopen the
+    /// budget. A no-op once `auto_mark_first` is `0` (the default) or its
+    /// budget is exhausted.
+    fn auto_mark_on_open(&mut self, project_path: ProjectPath, cx: &mut Context<Self>) {
+        let auto_mark_first = HarpoonSettings::get_global(cx).auto_mark_first;
+        if self.auto_marked_paths.len() >= auto_mark_first
+            || self.auto_marked_paths.contains(&project_path)
+        {
+            return;
+        }
+        self.auto_marked_paths.insert(project_path.clone());
+        self.mark(project_path, None, cx).log_err();
+    }
+
+    /// Returns the marks for the active scope: every mark in the project
+    /// under `HarpoonScope::Project`, or just the active worktree's marks
+    /// under `HarpoonScope::Worktree`. See [`Self::set_active_worktree`].
+    pub fn marks(&self, cx: &App) -> &[HarpoonMark] {
+        self.active_marks(cx)
+    }
+
+    /// Whether `project_path` is currently marked. Used to drive the
+    /// optional marked-file tab icon; see
+    /// [`HarpoonSettings::show_marked_indicator`].
+    pub fn is_marked(&self, project_path: &ProjectPath, cx: &App) -> bool {
+        self.active_marks(cx)
+            .iter()
+            .any(|mark| mark.target == HarpoonMarkTarget::File(project_path.clone()))
+    }
+
+    /// The slot currently holding `project_path`, if any. The same lookup as
+    /// [`Self::is_marked`], just keeping the position instead of discarding
+    /// it.
+    pub fn slot_for(&self, project_path: &ProjectPath, cx: &App) -> Option<usize> {
+        self.active_marks(cx)
+            .iter()
+            .position(|mark| mark.target == HarpoonMarkTarget::File(project_path.clone()))
+    }
+
+    /// Returns the current marks as quick-action descriptors, for UI outside
+    /// this crate (e.g. a context menu offering "jump to marked file") to
+    /// list and invoke without reaching into [`Self::marks`] directly.
+    /// Callers should regenerate this list on [`MarksChanged`] to stay
+    /// current, the same way [`HarpoonPicker`] regenerates its matches.
+    pub fn quick_actions(&self, cx: &App) -> Vec<HarpoonQuickAction> {
+        self.active_marks(cx)
+            .iter()
+            .map(|mark| HarpoonQuickAction {
+                label: mark.target.display_label().into(),
+                target: mark.target.clone(),
+            })
+            .collect()
+    }
+
+    /// The slot most recently populated by [`Self::mark`], if any mark has
+    /// been added since this store was created.
+    pub fn last_added_slot(&self) -> Option<usize> {
+        self.last_added_slot
+    }
+
+    /// The slot last reported by [`ActiveMarkChanged`], i.e. the one holding
+    /// the active file, if any.
+    pub fn active_mark_slot(&self) -> Option<usize> {
+        self.active_mark_slot
+    }
+
+    /// Returns a fully-owned [`HarpoonSnapshot`] of the store's current
+    /// state. See [`HarpoonSnapshot`] for why this exists alongside
+    /// [`Self::marks`].
+    pub fn snapshot(&self, cx: &App) -> HarpoonSnapshot {
+        HarpoonSnapshot {
+            marks: self
+                .active_marks(cx)
+                .iter()
+                .enumerate()
+                .map(|(slot, mark)| HarpoonMarkSnapshot {
+                    slot,
+                    id: mark.id,
+                    target: mark.target.clone(),
+                })
+                .collect(),
+            active_list_name: None,
+            max_slots: HarpoonSettings::get_global(cx).max_slots,
+        }
+    }
+
+    fn allocate_mark_id(&mut self) -> HarpoonMarkId {
+        let id = HarpoonMarkId(self.next_mark_id);
+        self.next_mark_id += 1;
+        id
+    }
+
+    /// Adds `project_path` as a new mark, unless it's already marked or the
+    /// project has reached `HarpoonSettings::max_slots`. Returns whether a
+    /// mark was added. Fails if `project_path` doesn't belong to one of the
+    /// project's worktrees, since a mark outside the project can't be
+    /// resolved again on persistence reload.
+    pub fn mark(
+        &mut self,
+        project_path: ProjectPath,
+        cursor: Option<HarpoonCursor>,
+        cx: &mut Context<Self>,
+    ) -> Result<bool> {
+        let Some(project) = self.project.upgrade() else {
+            return Err(anyhow!(
+                "cannot mark {:?}: project has been dropped",
+                project_path.path
+            ));
+        };
+        if project
+            .read(cx)
+            .worktree_for_id(project_path.worktree_id, cx)
+            .is_none()
+        {
+            return Err(anyhow!(
+                "cannot mark {:?}: not part of this project",
+                project_path.path
+            ));
+        }
+        let entry_id = project
+            .read(cx)
+            .entry_for_path(&project_path, cx)
+            .map(|entry| entry.id);
+        let target = HarpoonMarkTarget::File(project_path);
+        let marks = self.active_marks_mut(cx);
+        if marks.iter().any(|mark| mark.target == target) {
+            return Ok(false);
+        }
+        if marks.len() >= HarpoonSettings::get_global(cx).max_slots {
+            return Ok(false);
+        }
+        let id = self.allocate_mark_id();
+        let marks = self.active_marks_mut(cx);
+        marks.push(HarpoonMark {
+            id,
+            target,
+            entry_id,
+            last_jumped: None,
+            jump_count: 0,
+            cursor,
+            comment: None,
+        });
+        self.last_added_slot = Some(self.active_marks(cx).len() - 1);
+        self.persist_marks(cx);
+        cx.emit(MarksChanged);
+        cx.notify();
+        Ok(true)
+    }
+
+    /// Adds `cwd` as a new terminal mark, unless a terminal mark with the
+    /// same working directory already exists or the project has reached
+    /// `HarpoonSettings::max_slots`. Returns whether a mark was added.
+    /// Unlike [`Self::mark`], there's no project-membership check: a
+    /// terminal's working directory isn't required to live inside the
+    /// project.
+    pub fn mark_terminal(&mut self, cwd: Arc<Path>, cx: &mut Context<Self>) -> bool {
+        let target = HarpoonMarkTarget::Terminal(cwd);
+        let marks = self.active_marks_mut(cx);
+        if marks.iter().any(|mark| mark.target == target) {
+            return false;
+        }
+        if marks.len() >= HarpoonSettings::get_global(cx).max_slots {
+            return false;
+        }
+        let id = self.allocate_mark_id();
+        let marks = self.active_marks_mut(cx);
+        marks.push(HarpoonMark {
+            id,
+            target,
+            entry_id: None,
+            last_jumped: None,
+            jump_count: 0,
+            cursor: None,
+            comment: None,
+        });
+        self.last_added_slot = Some(self.active_marks(cx).len() - 1);
+        self.persist_marks(cx);
+        cx.emit(MarksChanged);
+        cx.notify();
+        true
+    }
+
+    /// Places `project_path` into `slot` (0-indexed), replacing whatever mark
+    /// is currently there. The deterministic counterpart to [`Self::mark`]'s
+    /// first-empty-slot behavior. If `project_path` is already marked
+    /// elsewhere, that mark is swapped into the slot being overwritten
+    /// instead of being dropped, so every other mark keeps its existing
+    /// slot. `slot` may be at most the current number of marks (extending by
+    /// one, like `mark`); anything further out would leave a gap the slot
+    /// model can't represent. When `slot` is that one-past-the-end slot and
+    /// `project_path` is already marked, its old mark is removed and the path
+    /// appended, so the marks after the old one each move down a slot and the
+    /// path lands one slot before `slot`. Fails if `project_path` doesn't belong to one
+    /// of the project's worktrees, or `slot` is out of range.
+    pub fn set_slot(
+        &mut self,
+        slot: usize,
+        project_path: ProjectPath,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let Some(project) = self.project.upgrade() else {
+            return Err(anyhow!("cannot set slot {slot}: project has been dropped"));
+        };
+        if project
+            .read(cx)
+            .worktree_for_id(project_path.worktree_id, cx)
+            .is_none()
+        {
+            return Err(anyhow!(
+                "cannot set slot {slot}: {:?} is not part of this project",
+                project_path.path
+            ));
+        }
+        if slot >= HarpoonSettings::get_global(cx).max_slots {
+            return Err(anyhow!(
+                "cannot set slot {slot}: beyond the configured max_slots"
+            ));
+        }
+        if slot > self.active_marks(cx).len() {
+            return Err(anyhow!(
+                "cannot set slot {slot}: only {} mark(s) exist so far",
+                self.active_marks(cx).len()
+            ));
+        }
+        let entry_id = project
+            .read(cx)
+            .entry_for_path(&project_path, cx)
+            .map(|entry| entry.id);
+        let target = HarpoonMarkTarget::File(project_path);
+        let mark = HarpoonMark {
+            id: self.allocate_mark_id(),
+            target: target.clone(),
+            entry_id,
+            last_jumped: None,
+            jump_count: 0,
+            cursor: None,
+            comment: None,
+        };
+        let marks = self.active_marks_mut(cx);
+        if slot == marks.len() {
+            // Nothing occupies `slot` yet to swap with, so just drop the
+            // stale duplicate (if any) before appending.
+            let existing_index = marks.iter().position(|existing| existing.target == target);
+            if let Some(existing_index) = existing_index {
+                marks.remove(existing_index);
+            }
+            marks.push(mark);
+        } else {
+            // If this path is already marked elsewhere, swap it into `slot`
+            // instead of overwriting-then-pruning: pruning the stale
+            // duplicate afterward would compact the vector and shift every
+            // mark past it down by one slot, none of which asked to move.
+            match marks.iter().position(|existing| existing.target == target) {
+                Some(existing_index) if existing_index != slot => {
+                    let displaced = std::mem::replace(&mut marks[slot], mark);
+                    marks[existing_index] = displaced;
+                }
+                _ => marks[slot] = mark,
+            }
+        }
+        Self::dedupe_file_paths(marks);
+        self.last_added_slot = self.active_marks(cx).iter().position(|mark| mark.target == target);
+        self.persist_marks(cx);
+        cx.emit(MarksChanged);
+        cx.notify();
+        Ok(())
+    }
+
+    /// Removes the mark with `mark_id`, shifting later marks down to fill the
+    /// gap it leaves in slot order. Returns `false` if no mark has that id
+    /// (e.g. it was already removed).
+    pub fn remove_mark(&mut self, mark_id: HarpoonMarkId, cx: &mut Context<Self>) -> bool {
+        let marks = self.active_marks_mut(cx);
+        let Some(index) = marks.iter().position(|mark| mark.id == mark_id) else {
+            return false;
+        };
+        marks.remove(index);
+        self.persist_marks(cx);
+        cx.emit(MarksChanged);
+        cx.notify();
+        true
+    }
+
+    /// Removes every [`HarpoonMarkTarget::File`] mark whose path matches
+    /// `matcher`, shifting later marks down to fill the gaps left in slot
+    /// order, the same as [`Self::remove_mark`]. Terminal marks are never
+    /// matched. Returns the number of marks removed; a no-op (no event
+    /// emitted) if none matched.
+    pub fn remove_matching(&mut self, matcher: &PathMatcher, cx: &mut Context<Self>) -> usize {
+        let marks = self.active_marks_mut(cx);
+        let before = marks.len();
+        marks.retain(|mark| match &mark.target {
+            HarpoonMarkTarget::File(project_path) => !matcher.is_match(&project_path.path),
+            HarpoonMarkTarget::Terminal(_) => true,
+        });
+        let removed = before - marks.len();
+        if removed > 0 {
+            cx.emit(MarksChanged);
+            cx.notify();
+            self.persist_marks(cx);
+        }
+        removed
+    }
+
+    /// Removes a [`HarpoonMarkTarget::File`] mark if an earlier mark in
+    /// `marks` already targets the same path, keeping the lower-slot
+    /// occurrence and logging a warning for each one dropped. Every mutating
+    /// method that can replace the active mark set wholesale (rather than
+    /// just appending or removing a single mark, which can't introduce a
+    /// duplicate) calls this before emitting [`MarksChanged`], so
+    /// [`Self::is_marked`]'s assumption that at most one mark matches a
+    /// given path keeps holding as more mutation paths are added.
+    fn dedupe_file_paths(marks: &mut Vec<HarpoonMark>) {
+        let mut seen_paths = HashSet::default();
+        marks.retain(|mark| match &mark.target {
+            HarpoonMarkTarget::File(project_path) => {
+                if seen_paths.insert(project_path.clone()) {
+                    true
+                } else {
+                    log::warn!(
+                        "harpoon: coalescing duplicate mark for {:?}, keeping the lower slot",
+                        project_path.path
+                    );
+                    false
+                }
+            }
+            HarpoonMarkTarget::Terminal(_) => true,
+        });
+    }
+
+    /// Swaps the slots of the marks with `first` and `second`, reordering
+    /// them without affecting any other mark. A no-op if either id doesn't
+    /// exist.
+    pub fn swap_marks(&mut self, first: HarpoonMarkId, second: HarpoonMarkId, cx: &mut Context<Self>) {
+        let marks = self.active_marks_mut(cx);
+        let Some(first_index) = marks.iter().position(|mark| mark.id == first) else {
+            return;
+        };
+        let Some(second_index) = marks.iter().position(|mark| mark.id == second) else {
+            return;
+        };
+        self.push_reorder_snapshot(cx);
+        let marks = self.active_marks_mut(cx);
+        marks.swap(first_index, second_index);
+        Self::dedupe_file_paths(marks);
+        self.persist_marks(cx);
+        cx.emit(MarksChanged);
+        cx.notify();
+    }
+
+    /// Pushes the active mark set's current order onto
+    /// [`Self::reorder_undo_stack`], dropping the oldest snapshot once
+    /// [`REORDER_UNDO_STACK_CAP`] is exceeded. Call before applying a
+    /// reorder, not after.
+    fn push_reorder_snapshot(&mut self, cx: &App) {
+        if self.reorder_undo_stack.len() >= REORDER_UNDO_STACK_CAP {
+            self.reorder_undo_stack.remove(0);
+        }
+        self.reorder_undo_stack.push(self.active_marks(cx).to_vec());
+    }
+
+    /// Pops the most recent reorder snapshot and restores the active mark
+    /// set's order to it. Returns whether there was anything to undo.
+    pub fn undo_reorder(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(mut marks) = self.reorder_undo_stack.pop() else {
+            return false;
+        };
+        Self::dedupe_file_paths(&mut marks);
+        self.set_active_marks(marks, cx);
+        self.persist_marks(cx);
+        cx.emit(MarksChanged);
+        cx.notify();
+        true
+    }
+
+    /// Reorders every occupied mark per
+    /// `HarpoonSettings::normalize_slots_order` and compacts them into slots
+    /// `1..N`. Undoable via [`Self::undo_reorder`], the same as a manual
+    /// swap. A no-op if there are fewer than two marks.
+    pub fn normalize_slots(&mut self, cx: &mut Context<Self>) {
+        let mut marks = self.active_marks(cx).to_vec();
+        if marks.len() < 2 {
+            return;
+        }
+        match HarpoonSettings::get_global(cx).normalize_slots_order {
+            HarpoonNormalizeSlotsOrder::Path => {
+                marks.sort_by(|a, b| a.target.display_label().cmp(&b.target.display_label()));
+            }
+            HarpoonNormalizeSlotsOrder::MarkOrder => {
+                marks.sort_by_key(|mark| mark.id.0);
+            }
+        }
+        self.push_reorder_snapshot(cx);
+        Self::dedupe_file_paths(&mut marks);
+        self.set_active_marks(marks, cx);
+        cx.emit(MarksChanged);
+        cx.notify();
+        self.persist_marks(cx);
+    }
+
+    /// Records that `mark_id` was just jumped to, so the picker can list it
+    /// first when `HarpoonSettings::picker_sort` is `Recent`, tallies it
+    /// towards `mark_id`'s `jump_count`, shown in the picker when
+    /// `HarpoonSettings::show_jump_counts` is enabled, pushes its target onto
+    /// [`Self::jump_ring`] for [`ToggleLastTwo`], and appends it to
+    /// [`Self::jump_history`] for [`JumpBack`]/[`JumpForward`]. A no-op if the
+    /// mark has since been removed.
+    pub fn record_jump(&mut self, mark_id: HarpoonMarkId, cx: &mut Context<Self>) {
+        let Some(mark) = self
+            .active_marks_mut(cx)
+            .iter_mut()
+            .find(|mark| mark.id == mark_id)
+        else {
+            return;
+        };
+        mark.last_jumped = Some(Instant::now());
+        mark.jump_count += 1;
+        let target = mark.target.clone();
+        self.push_jump_ring(target.clone());
+        self.push_jump_history(target);
+        cx.emit(MarksChanged);
+    }
+
+    /// Pushes `target` onto the front of [`Self::jump_ring`], the two-entry
+    /// history behind [`ToggleLastTwo`], shifting the previous front entry
+    /// back. A no-op if `target` is already the front entry, so jumping to
+    /// the current file twice in a row doesn't collapse the ring to a single
+    /// repeated entry.
+    fn push_jump_ring(&mut self, target: HarpoonMarkTarget) {
+        if self.jump_ring[0].as_ref() == Some(&target) {
+            return;
+        }
+        self.jump_ring[1] = self.jump_ring[0].take();
+        self.jump_ring[0] = Some(target);
+    }
+
+    /// The other entry in [`Self::jump_ring`] — the most recently
+    /// harpoon-jumped-to target before the current one — for
+    /// [`ToggleLastTwo`] to flip back to. Independent of the numbered slots:
+    /// the alternate target doesn't need to still be an active mark.
+    pub fn alternate_jump_target(&self) -> Option<HarpoonMarkTarget> {
+        self.jump_ring[1].clone()
+    }
+
+    /// Appends `target` to [`Self::jump_history`], truncating anything past
+    /// the current cursor first — so jumping somewhere new after
+    /// [`Self::jump_back`] discards the stale forward trail, the same way a
+    /// browser's history works. A no-op if `target` is already the most
+    /// recent entry. Drops the oldest entry once [`JUMP_HISTORY_CAP`] is
+    /// exceeded.
+    fn push_jump_history(&mut self, target: HarpoonMarkTarget) {
+        self.jump_history
+            .truncate(self.jump_history_cursor.map_or(0, |cursor| cursor + 1));
+        if self.jump_history.last() == Some(&target) {
+            self.jump_history_cursor = Some(self.jump_history.len() - 1);
+            return;
+        }
+        self.jump_history.push(target);
+        if self.jump_history.len() > JUMP_HISTORY_CAP {
+            self.jump_history.remove(0);
+        }
+        self.jump_history_cursor = Some(self.jump_history.len() - 1);
+    }
+
+    /// Steps one entry back through [`Self::jump_history`] for [`JumpBack`],
+    /// returning the target to jump to. Clamped at the start of the history:
+    /// returns `None` without moving the cursor once there's nowhere further
+    /// back to go.
+    pub fn jump_back(&mut self) -> Option<HarpoonMarkTarget> {
+        let new_cursor = self.jump_history_cursor?.checked_sub(1)?;
+        self.jump_history_cursor = Some(new_cursor);
+        self.jump_history.get(new_cursor).cloned()
+    }
+
+    /// The reverse of [`Self::jump_back`], for [`JumpForward`]. Clamped at
+    /// the most recent entry: returns `None` without moving the cursor once
+    /// there's nowhere further forward to go.
+    pub fn jump_forward(&mut self) -> Option<HarpoonMarkTarget> {
+        let new_cursor = self.jump_history_cursor?.checked_add(1)?;
+        let target = self.jump_history.get(new_cursor)?.clone();
+        self.jump_history_cursor = Some(new_cursor);
+        Some(target)
+    }
+
+    /// Sets or clears `mark_id`'s comment, e.g. from `HarpoonCommentInput`
+    /// after a [`PromptForMarkComment`] prompt. A no-op if the mark has since
+    /// been removed.
+    pub fn set_comment(
+        &mut self,
+        mark_id: HarpoonMarkId,
+        comment: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(mark) = self
+            .active_marks_mut(cx)
+            .iter_mut()
+            .find(|mark| mark.id == mark_id)
+        else {
+            return;
+        };
+        mark.comment = comment;
+        cx.emit(MarksChanged);
+        cx.notify();
+        self.persist_marks(cx);
+    }
+
+    /// Re-derives the project path of every file mark whose entry moved
+    /// (e.g. a rename or a move into another directory) from its stable
+    /// [`ProjectEntryId`], so marks keep pointing at the right file instead of
+    /// going stale after a rename. Terminal marks have no `entry_id` and are
+    /// left untouched. Called automatically on worktree updates, but also
+    /// exposed so callers can force a refresh after a bulk move. There's no
+    /// separate cached display name to go stale here — the picker already
+    /// derives its display text from the mark's target on every render — so
+    /// keeping the target itself current is the whole fix.
+    pub fn refresh_marks(&mut self, cx: &mut Context<Self>) {
+        let Some(project) = self.project.upgrade() else {
+            return;
+        };
+        let mut changed = false;
+        for marks in self.marks_by_scope.values_mut() {
+            for mark in marks {
+                let Some(entry_id) = mark.entry_id else {
+                    continue;
+                };
+                let Some(current_path) = project.read(cx).path_for_entry(entry_id, cx) else {
+                    continue;
+                };
+                if mark.target != HarpoonMarkTarget::File(current_path.clone()) {
+                    mark.target = HarpoonMarkTarget::File(current_path);
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            cx.emit(MarksChanged);
+            cx.notify();
+        }
+    }
+
+    /// Schedules `target` to be marked after `window` elapses, unless
+    /// [`Self::cancel_pending_mark`] is called first (the double-tap case).
+    pub fn schedule_mark(
+        &mut self,
+        target: HarpoonMarkTarget,
+        cursor: Option<HarpoonCursor>,
+        window: Duration,
+        cx: &mut Context<Self>,
+    ) {
+        self.pending_mark = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(window).await;
+            this.update(cx, |this, cx| {
+                this.pending_mark = None;
+                let added = match target {
+                    HarpoonMarkTarget::File(project_path) => {
+                        this.mark(project_path, cursor, cx).log_err().unwrap_or(false)
+                    }
+                    HarpoonMarkTarget::Terminal(cwd) => this.mark_terminal(cwd, cx),
+                };
+                if added {
+                    this.prompt_for_comment_if_enabled(cx);
+                }
+            })
+            .ok();
+        }));
+    }
+
+    /// Emits [`PromptForMarkComment`] for the mark most recently added via
+    /// [`Self::last_added_slot`], if `HarpoonSettings::prompt_on_mark` is
+    /// enabled. A no-op otherwise, so the fast path of marking without a note
+    /// stays fast.
+    fn prompt_for_comment_if_enabled(&mut self, cx: &mut Context<Self>) {
+        if !HarpoonSettings::get_global(cx).prompt_on_mark {
+            return;
+        }
+        let Some(mark_id) = self
+            .last_added_slot
+            .and_then(|slot| self.active_marks(cx).get(slot))
+            .map(|mark| mark.id)
+        else {
+            return;
+        };
+        cx.emit(PromptForMarkComment(mark_id));
+    }
+
+    /// Cancels a pending mark scheduled by [`Self::schedule_mark`], if one is
+    /// still outstanding. Returns whether one was cancelled.
+    pub fn cancel_pending_mark(&mut self) -> bool {
+        self.pending_mark.take().is_some()
+    }
+
+    /// Clears every mark, stashing them for
+    /// `HarpoonSettings::clear_undo_window_ms` so [`Self::restore_cleared_marks`]
+    /// can bring them back if this turns out to be a fat-fingered clear.
+    pub fn clear_all(&mut self, cx: &mut Context<Self>) {
+        if self.active_marks(cx).is_empty() {
+            return;
+        }
+        self.last_cleared = Some(self.take_active_marks(cx));
+        self.last_added_slot = None;
+        self.reorder_undo_stack.clear();
+        self.persist_marks(cx);
+        let undo_window =
+            Duration::from_millis(HarpoonSettings::get_global(cx).clear_undo_window_ms);
+        self._clear_undo_expiry = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(undo_window).await;
+            this.update(cx, |this, _cx| {
+                this.last_cleared = None;
+            })
+            .ok();
+        }));
+        cx.emit(MarksChanged);
+        cx.notify();
+    }
+
+    /// Restores the marks removed by the most recent [`Self::clear_all`], if
+    /// its undo window hasn't expired yet. Returns whether there was anything
+    /// to restore.
+    pub fn restore_cleared_marks(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(mut marks) = self.last_cleared.take() else {
+            return false;
+        };
+        self._clear_undo_expiry = None;
+        Self::dedupe_file_paths(&mut marks);
+        self.set_active_marks(marks, cx);
+        self.persist_marks(cx);
+        cx.emit(MarksChanged);
+        cx.notify();
+        true
+    }
+
+    /// Every `(worktree, worktree root, scope bucket)` this project could
+    /// have marks under, used by [`Self::load_persisted_marks`] and
+    /// [`Self::seed_from_project_file`] to resolve which worktrees to read
+    /// from. Mirrors [`Self::scope_key`]: one `Global` target for the first
+    /// worktree under `HarpoonScope::Project`, one `Worktree` target per
+    /// worktree under `HarpoonScope::Worktree`, plus a `Global` target for
+    /// the first worktree whenever `HarpoonScope::Worktree` has no active
+    /// worktree yet (e.g. before the first `ActiveItemChanged`) — the same
+    /// fallback `Self::scope_key` uses, so a mark made in that window is
+    /// seeded from and persisted to the same bucket it's actually stored in.
+    fn scope_targets(&self, cx: &App) -> Vec<(WorktreeId, Arc<Path>, HarpoonScopeKey)> {
+        let Some(project) = self.project.upgrade() else {
+            return Vec::new();
+        };
+        let worktrees: Vec<_> = project.read(cx).visible_worktrees(cx).collect();
+        match HarpoonSettings::get_global(cx).scope {
+            HarpoonScope::Project => worktrees
+                .first()
+                .map(|worktree| {
+                    let worktree = worktree.read(cx);
+                    (worktree.id(), worktree.abs_path(), HarpoonScopeKey::Global)
+                })
+                .into_iter()
+                .collect(),
+            HarpoonScope::Worktree => {
+                let mut targets: Vec<(WorktreeId, Arc<Path>, HarpoonScopeKey)> = worktrees
+                    .iter()
+                    .map(|worktree| {
+                        let worktree = worktree.read(cx);
+                        let id = worktree.id();
+                        (id, worktree.abs_path(), HarpoonScopeKey::Worktree(id))
+                    })
+                    .collect();
+                if self.active_worktree.is_none()
+                    && let Some(worktree) = worktrees.first()
+                {
+                    let worktree = worktree.read(cx);
+                    targets.push((worktree.id(), worktree.abs_path(), HarpoonScopeKey::Global));
+                }
+                targets
+            }
+        }
+    }
+
+    fn worktree_root_for_scope(&self, scope_key: HarpoonScopeKey, cx: &App) -> Option<Arc<Path>> {
+        self.scope_targets(cx)
+            .into_iter()
+            .find(|(_, _, key)| *key == scope_key)
+            .map(|(_, worktree_root, _)| worktree_root)
+    }
+
+    /// Writes the active scope's marks to [`db::kvp::KeyValueStore`] under
+    /// [`PERSISTED_MARKS_NAMESPACE`], keyed by [`persisted_marks_key`], so
+    /// they survive a restart. Called after every mutation that changes the
+    /// active mark set. A no-op if the scope's worktree can't be resolved
+    /// (e.g. the project has no worktrees yet).
+    fn persist_marks(&self, cx: &App) {
+        let scope_key = self.scope_key(cx);
+        let Some(worktree_root) = self.worktree_root_for_scope(scope_key, cx) else {
+            return;
+        };
+        let persisted = PersistedHarpoonMarks {
+            version: PERSISTED_MARKS_VERSION,
+            marks: self
+                .active_marks(cx)
+                .iter()
+                .map(|mark| PersistedHarpoonMark {
+                    id: Some(mark.id.0),
+                    target: match &mark.target {
+                        HarpoonMarkTarget::File(project_path) => PersistedHarpoonMarkTarget::File {
+                            path: Path::new(project_path.path.as_unix_str()).into(),
+                        },
+                        HarpoonMarkTarget::Terminal(cwd) => {
+                            PersistedHarpoonMarkTarget::Terminal { cwd: cwd.clone() }
+                        }
+                    },
+                    comment: mark.comment.clone(),
+                })
+                .collect(),
+        };
+        let Some(payload) = serde_json::to_string(&persisted).log_err() else {
+            return;
+        };
+        let store = KeyValueStore::global(cx);
+        let key = persisted_marks_key(&worktree_root, scope_key);
+        cx.background_spawn(async move {
+            store.scoped(PERSISTED_MARKS_NAMESPACE).write(key, payload).await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Loads each relevant scope's marks from [`db::kvp::KeyValueStore`],
+    /// synchronously since a scoped read doesn't need to await anything.
+    /// Called once when the store is created, before
+    /// [`Self::seed_from_project_file`] so a scope with real persisted marks
+    /// is already populated by the time that seed-file fallback's
+    /// first-run-only guard checks it.
+    fn load_persisted_marks(&mut self, cx: &mut Context<Self>) {
+        let targets = self.scope_targets(cx);
+        let store = KeyValueStore::global(cx);
+        for (worktree_id, worktree_root, scope_key) in targets {
+            let key = persisted_marks_key(&worktree_root, scope_key);
+            let Some(payload) = store
+                .scoped(PERSISTED_MARKS_NAMESPACE)
+                .read(&key)
+                .log_err()
+                .flatten()
+            else {
+                continue;
+            };
+            let Some(persisted) = serde_json::from_str::<PersistedHarpoonMarks>(&payload).log_err()
+            else {
+                continue;
+            };
+            let persisted = persisted.migrate();
+            let mut marks: Vec<HarpoonMark> = persisted
+                .marks
+                .iter()
+                .enumerate()
+                .filter_map(|(index, persisted_mark)| {
+                    let (target, entry_id) = match &persisted_mark.target {
+                        PersistedHarpoonMarkTarget::File { path } => {
+                            let path = util::rel_path::RelPath::new(
+                                path,
+                                util::paths::PathStyle::local(),
+                            )
+                            .log_err()?;
+                            let project_path = ProjectPath {
+                                worktree_id,
+                                path: path.into_owned().into_arc(),
+                            };
+                            let entry_id = self
+                                .project
+                                .upgrade()
+                                .and_then(|project| {
+                                    project.read(cx).entry_for_path(&project_path, cx)
+                                })
+                                .map(|entry| entry.id);
+                            (HarpoonMarkTarget::File(project_path), entry_id)
+                        }
+                        PersistedHarpoonMarkTarget::Terminal { cwd } => {
+                            (HarpoonMarkTarget::Terminal(cwd.clone()), None)
+                        }
+                    };
+                    Some(HarpoonMark {
+                        id: HarpoonMarkId(persisted_mark.id.unwrap_or(index as u32)),
+                        target,
+                        entry_id,
+                        last_jumped: None,
+                        jump_count: 0,
+                        cursor: None,
+                        comment: persisted_mark.comment.clone(),
+                    })
+                })
+                .collect();
+            if marks.is_empty() {
+                continue;
+            }
+            Self::dedupe_file_paths(&mut marks);
+            self.next_mark_id = self
+                .next_mark_id
+                .max(marks.iter().map(|mark| mark.id.0 + 1).max().unwrap_or(0));
+            self.marks_by_scope.insert(scope_key, marks);
+        }
+    }
+
+    /// Seeds this store from each relevant worktree's committed
+    /// `.harpoon.json` file, if one exists and no marks have been set yet for
+    /// that bucket. User-saved marks always win. Under `HarpoonScope::Project`
+    /// only the first worktree is seeded, into the shared
+    /// `HarpoonScopeKey::Global` bucket, matching pre-scoping behavior. Under
+    /// `HarpoonScope::Worktree`, every worktree is seeded independently from
+    /// its own seed file into its own bucket, so each root's marks persist
+    /// separately.
+    fn seed_from_project_file(&mut self, cx: &mut Context<Self>) {
+        for (worktree_id, worktree_root, scope_key) in self.scope_targets(cx) {
+            if self
+                .marks_by_scope
+                .get(&scope_key)
+                .is_some_and(|marks| !marks.is_empty())
+            {
+                continue;
+            }
+            let fs = self.fs.clone();
+            cx.spawn(async move |this, cx| {
+                let seed_path = worktree_root.join(SEED_FILE_NAME);
+                let contents = fs.load(&seed_path).await.ok()?;
+                let persisted: PersistedHarpoonMarks = serde_json::from_str(&contents).log_err()?;
+                let persisted = persisted.migrate();
+                this.update(cx, |this, cx| {
+                    if this
+                        .marks_by_scope
+                        .get(&scope_key)
+                        .is_some_and(|marks| !marks.is_empty())
+                    {
+                        // A user mark raced us while the seed file was loading; don't clobber it.
+                        return;
+                    }
+                    let mut marks: Vec<HarpoonMark> = persisted
+                        .marks
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, persisted_mark)| {
+                            let (target, entry_id) = match &persisted_mark.target {
+                                PersistedHarpoonMarkTarget::File { path } => {
+                                    let path = util::rel_path::RelPath::new(
+                                        path,
+                                        util::paths::PathStyle::local(),
+                                    )
+                                    .log_err()?;
+                                    let project_path = ProjectPath {
+                                        worktree_id,
+                                        path: path.into_owned().into_arc(),
+                                    };
+                                    let entry_id = this.project.upgrade().and_then(|project| {
+                                        project.read(cx).entry_for_path(&project_path, cx)
+                                    }).map(|entry| entry.id);
+                                    (HarpoonMarkTarget::File(project_path), entry_id)
+                                }
+                                PersistedHarpoonMarkTarget::Terminal { cwd } => {
+                                    (HarpoonMarkTarget::Terminal(cwd.clone()), None)
+                                }
+                            };
+                            Some(HarpoonMark {
+                                id: HarpoonMarkId(persisted_mark.id.unwrap_or(index as u32)),
+                                target,
+                                entry_id,
+                                last_jumped: None,
+                                jump_count: 0,
+                                cursor: None,
+                                comment: persisted_mark.comment.clone(),
+                            })
+                        })
+                        .collect();
+                    this.next_mark_id = this.next_mark_id.max(
+                        marks.iter().map(|mark| mark.id.0 + 1).max().unwrap_or(0),
+                    );
+                    // A hand-edited seed file could list the same path twice.
+                    Self::dedupe_file_paths(&mut marks);
+                    this.marks_by_scope.insert(scope_key, marks);
+                    cx.emit(MarksChanged);
+                    cx.notify();
+                })
+                .ok();
+                Some(())
+            })
+            .detach();
+        }
+    }
+}
+
+impl EventEmitter<MarksChanged> for HarpoonStore {}
+impl EventEmitter<PromptForMarkComment> for HarpoonStore {}
+impl EventEmitter<ActiveMarkChanged> for HarpoonStore {}
+
+/// Identifies a project's harpoon store independent of the lifetime of any
+/// particular `Entity<Project>`. Unlike `EntityId`, this stays the same
+/// across a project entity being torn down and recreated for the same
+/// worktrees (e.g. a window reload), so [`get_or_create_harpoon_store`] can
+/// hand back the existing store instead of a fresh, empty one and silently
+/// dropping in-memory marks that haven't been persisted yet.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum HarpoonProjectKey {
+    /// The sorted absolute paths of the project's visible worktrees.
+    Worktrees(Vec<Arc<Path>>),
+    /// No worktrees are open yet, so there's nothing stable to key on;
+    /// falls back to entity identity like the old scheme.
+    Entity(EntityId),
+}
+
+impl HarpoonProjectKey {
+    fn for_project(project: &Entity<Project>, cx: &App) -> Self {
+        let mut worktree_paths: Vec<Arc<Path>> = project
+            .read(cx)
+            .visible_worktrees(cx)
+            .map(|worktree| worktree.read(cx).abs_path())
+            .collect();
+        if worktree_paths.is_empty() {
+            return Self::Entity(project.entity_id());
+        }
+        worktree_paths.sort();
+        Self::Worktrees(worktree_paths)
+    }
+}
+
+struct GlobalHarpoonStoreEntry {
+    store: Entity<HarpoonStore>,
+    /// Set the first time a sweep in [`get_or_create_harpoon_store`] finds
+    /// this entry's project entity has been dropped. Cleared again as soon
+    /// as a live project resolves to this key. Entries still orphaned after
+    /// [`ORPHANED_STORE_EVICTION_WINDOW`] are reaped so a project that's
+    /// closed for good doesn't leak its store forever.
+    orphaned_since: Option<Instant>,
+}
+
+struct GlobalHarpoonStore(HashMap<HarpoonProjectKey, GlobalHarpoonStoreEntry>);
+
+impl Global for GlobalHarpoonStore {}
+
+/// How long an orphaned store is kept around in case the same worktrees
+/// reappear under a new project entity shortly after (e.g. a window
+/// reload) and should reuse it instead of starting from an empty,
+/// reseeded store.
+const ORPHANED_STORE_EVICTION_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// Returns the harpoon store for `project`, creating (and seeding) one if this
+/// is the first time it's been requested for that project. Keyed by
+/// [`HarpoonProjectKey`] rather than the project entity's id, so transient
+/// project churn doesn't discard in-memory marks before persistence catches
+/// up.
+pub fn get_or_create_harpoon_store(project: &Entity<Project>, cx: &mut App) -> Entity<HarpoonStore> {
+    let key = HarpoonProjectKey::for_project(project, cx);
+    evict_orphaned_harpoon_stores(cx);
+
+    if let Some(global) = cx.try_global::<GlobalHarpoonStore>()
+        && let Some(entry) = global.0.get(&key)
+    {
+        let store = entry.store.clone();
+        if *project != store.read(cx).project {
+            store.update(cx, |store, cx| store.rebind_project(project.clone(), cx));
+        }
+        cx.global_mut::<GlobalHarpoonStore>()
+            .0
+            .get_mut(&key)
+            .expect("just looked up by this key")
+            .orphaned_since = None;
+        return store;
+    }
+
+    let fs = project.read(cx).fs().clone();
+    let project = project.clone();
+    let store = cx.new(|cx| {
+        let mut store = HarpoonStore::new(project, fs, cx);
+        store.load_persisted_marks(cx);
+        store.seed_from_project_file(cx);
+        store
+    });
+
+    cx.default_global::<GlobalHarpoonStore>().0.insert(
+        key,
+        GlobalHarpoonStoreEntry {
+            store: store.clone(),
+            orphaned_since: None,
+        },
+    );
+    store
+}
+
+/// Marks entries whose project entity has been dropped, and reaps ones that
+/// have stayed orphaned past [`ORPHANED_STORE_EVICTION_WINDOW`].
+fn evict_orphaned_harpoon_stores(cx: &mut App) {
+    let Some(global) = cx.try_global::<GlobalHarpoonStore>() else {
+        return;
+    };
+    let liveness: Vec<(HarpoonProjectKey, bool)> = global
+        .0
+        .iter()
+        .map(|(key, entry)| {
+            (
+                key.clone(),
+                entry.store.read(cx).project.upgrade().is_some(),
+            )
+        })
+        .collect();
+
+    let now = Instant::now();
+    let global = cx.global_mut::<GlobalHarpoonStore>();
+    for (key, is_alive) in liveness {
+        let Some(entry) = global.0.get_mut(&key) else {
+            continue;
+        };
+        if is_alive {
+            entry.orphaned_since = None;
+            continue;
+        }
+        let orphaned_since = *entry.orphaned_since.get_or_insert(now);
+        if now.duration_since(orphaned_since) >= ORPHANED_STORE_EVICTION_WINDOW {
+            global.0.remove(&key);
+        }
+    }
+}
+
+/// Extends [`Entity<Project>`] with access to its harpoon marks, so other
+/// crates can reach the store without reimplementing the global lookup.
+pub trait ProjectHarpoonExt {
+    /// Returns the harpoon store for this project, creating (and seeding) one
+    /// if this is the first time it's been requested.
+    fn harpoon_store(&self, cx: &mut App) -> Entity<HarpoonStore>;
+}
+
+impl ProjectHarpoonExt for Entity<Project> {
+    fn harpoon_store(&self, cx: &mut App) -> Entity<HarpoonStore> {
+        get_or_create_harpoon_store(self, cx)
+    }
+}
+
+/// Renders a bookmark icon after a tab's title when that tab's file is
+/// marked, re-rendering on [`MarksChanged`] so it stays current.
+struct HarpoonTabIndicatorAddon {
+    store: Entity<HarpoonStore>,
+    _marks_changed_subscription: Subscription,
+}
+
+impl editor::Addon for HarpoonTabIndicatorAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn render_tab_icon(&self, buffer: &language::BufferSnapshot, cx: &App) -> Option<AnyElement> {
+        let project_path = project_path_for_buffer_snapshot(buffer, cx)?;
+        if !self.store.read(cx).is_marked(&project_path, cx) {
+            return None;
+        }
+        Some(
+            Icon::new(IconName::Bookmark)
+                .size(IconSize::XSmall)
+                .color(Color::Muted)
+                .into_any_element(),
+        )
+    }
+}
+
+/// Mirrors `project::ProjectItem::project_path` for a [`language::Buffer`],
+/// but for a [`language::BufferSnapshot`], which doesn't implement that
+/// trait.
+fn project_path_for_buffer_snapshot(
+    buffer: &language::BufferSnapshot,
+    cx: &App,
+) -> Option<ProjectPath> {
+    let file = buffer.file()?;
+    (!matches!(file.disk_state(), language::DiskState::Historic { .. })).then(|| ProjectPath {
+        worktree_id: file.worktree_id(cx),
+        path: file.path().clone(),
+    })
+}
+
+fn register_marked_tab_indicator(editor: &mut Editor, cx: &mut Context<Editor>) {
+    if !HarpoonSettings::get_global(cx).show_marked_indicator {
+        return;
+    }
+    let Some(project) = editor.project().cloned() else {
+        return;
+    };
+    let store = get_or_create_harpoon_store(&project, cx);
+    let marks_changed_subscription =
+        cx.subscribe(&store, |_editor, _store, _: &MarksChanged, cx| cx.notify());
+    editor.register_addon(HarpoonTabIndicatorAddon {
+        store,
+        _marks_changed_subscription: marks_changed_subscription,
+    });
+}
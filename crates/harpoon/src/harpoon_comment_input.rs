@@ -0,0 +1,132 @@
+use editor::{Editor, EditorEvent};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription,
+    Window,
+};
+use theme::ActiveTheme;
+use ui::prelude::*;
+use workspace::{ModalView, Workspace};
+
+use crate::{HarpoonMarkId, HarpoonStore, PromptForMarkComment, get_or_create_harpoon_store};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        if let Some(window) = window {
+            let store = get_or_create_harpoon_store(workspace.project(), cx);
+            cx.subscribe_in(&store, window, {
+                move |workspace, store, event: &PromptForMarkComment, window, cx| {
+                    HarpoonCommentInput::toggle(workspace, store.clone(), event.0, window, cx);
+                }
+            })
+            .detach();
+        }
+    })
+    .detach();
+}
+
+/// A minimal modal that prompts for a short note on a just-added mark, opened
+/// by [`PromptForMarkComment`] when `HarpoonSettings::prompt_on_mark` is
+/// enabled. Confirming with an empty input clears the mark's comment rather
+/// than leaving it untouched, so a prompt opened by mistake can be dismissed
+/// with a bare `Enter`.
+pub struct HarpoonCommentInput {
+    store: Entity<HarpoonStore>,
+    mark_id: HarpoonMarkId,
+    input_editor: Entity<Editor>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl HarpoonCommentInput {
+    pub fn toggle(
+        workspace: &mut Workspace,
+        store: Entity<HarpoonStore>,
+        mark_id: HarpoonMarkId,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(store, mark_id, window, cx)
+        });
+    }
+
+    fn new(
+        store: Entity<HarpoonStore>,
+        mark_id: HarpoonMarkId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let input_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Note for this mark…", window, cx);
+            editor
+        });
+        let input_editor_change =
+            cx.subscribe_in(&input_editor, window, Self::on_input_editor_event);
+        Self {
+            store,
+            mark_id,
+            input_editor,
+            _subscriptions: vec![input_editor_change],
+        }
+    }
+
+    fn on_input_editor_event(
+        &mut self,
+        _: &Entity<Editor>,
+        event: &EditorEvent,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if matches!(event, EditorEvent::Blurred) {
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let typed_comment = self.input_editor.read(cx).text(cx);
+        let comment = Some(typed_comment.trim().to_string()).filter(|text| !text.is_empty());
+        let mark_id = self.mark_id;
+        self.store.update(cx, |store, cx| {
+            store.set_comment(mark_id, comment, cx);
+        });
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for HarpoonCommentInput {}
+
+impl ModalView for HarpoonCommentInput {}
+
+impl Focusable for HarpoonCommentInput {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input_editor.focus_handle(cx)
+    }
+}
+
+impl Render for HarpoonCommentInput {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("HarpoonCommentInput")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(self.input_editor.clone()),
+            )
+            .child(
+                h_flex().px_2().py_1().gap_1().child(
+                    Label::new("Add a note for this mark, Enter to save").color(Color::Muted),
+                ),
+            )
+    }
+}
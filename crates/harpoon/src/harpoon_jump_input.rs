@@ -0,0 +1,133 @@
+use editor::{Editor, EditorEvent};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription,
+    WeakEntity, Window,
+};
+use theme::ActiveTheme;
+use ui::prelude::*;
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use crate::{HarpoonStore, ToggleHarpoonJumpInput, get_or_create_harpoon_store};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleHarpoonJumpInput, window, cx| {
+            HarpoonJumpInput::toggle(workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// A minimal modal that jumps to a mark by typed slot number, for fast
+/// sequential navigation without opening the full [`crate::HarpoonPicker`].
+/// Slots are entered 1-indexed, matching how marks are counted when reading
+/// the picker list, and translated to the 0-indexed [`crate::JumpToSlot`]
+/// numbering internally.
+pub struct HarpoonJumpInput {
+    store: Entity<HarpoonStore>,
+    workspace: WeakEntity<Workspace>,
+    input_editor: Entity<Editor>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl HarpoonJumpInput {
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let store = get_or_create_harpoon_store(workspace.project(), cx);
+        let weak_workspace = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(store, weak_workspace, window, cx)
+        });
+    }
+
+    fn new(
+        store: Entity<HarpoonStore>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let input_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Slot number…", window, cx);
+            editor
+        });
+        let input_editor_change =
+            cx.subscribe_in(&input_editor, window, Self::on_input_editor_event);
+        Self {
+            store,
+            workspace,
+            input_editor,
+            _subscriptions: vec![input_editor_change],
+        }
+    }
+
+    fn on_input_editor_event(
+        &mut self,
+        _: &Entity<Editor>,
+        event: &EditorEvent,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if matches!(event, EditorEvent::Blurred) {
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let typed_slot = self.input_editor.read(cx).text(cx);
+        if let Some(slot) = typed_slot
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|slot| slot.checked_sub(1))
+            && let Some(mark) = self.store.read(cx).marks(cx).get(slot).cloned()
+        {
+            self.workspace
+                .update_in(cx, |workspace, window, cx| {
+                    crate::open_mark(workspace, mark.target, mark.cursor, window, cx);
+                })
+                .log_err();
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for HarpoonJumpInput {}
+
+impl ModalView for HarpoonJumpInput {}
+
+impl Focusable for HarpoonJumpInput {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input_editor.focus_handle(cx)
+    }
+}
+
+impl Render for HarpoonJumpInput {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(20.))
+            .elevation_2(cx)
+            .key_context("HarpoonJumpInput")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(self.input_editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .gap_1()
+                    .child(Label::new("Type a slot number, Enter to jump").color(Color::Muted)),
+            )
+    }
+}
@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use fs::Fs;
+use gpui::{
+    Action, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable, Pixels,
+    Render, Subscription, WeakEntity, Window,
+};
+use settings::Settings as _;
+use ui::{Label, LabelSize, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+use crate::{
+    HarpoonMarkTarget, HarpoonSettings, HarpoonStore, MarksChanged, ToggleHarpoonPanel,
+    get_or_create_harpoon_store, open_mark,
+};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleHarpoonPanel, window, cx| {
+            workspace.toggle_panel_focus::<HarpoonPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+/// A dockable, always-visible alternative to [`crate::HarpoonPicker`]'s
+/// transient modal: the same marks, persistently on screen.
+pub struct HarpoonPanel {
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    store: Entity<HarpoonStore>,
+    selected_index: Option<usize>,
+    activation_priority: u32,
+    _store_subscription: Subscription,
+}
+
+impl HarpoonPanel {
+    pub fn new(workspace: &mut Workspace, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let store = get_or_create_harpoon_store(workspace.project(), cx);
+        let store_subscription = cx.subscribe(&store, |_this, _store, _: &MarksChanged, cx| {
+            cx.notify();
+        });
+        Self {
+            workspace: workspace.weak_handle(),
+            fs: workspace.app_state().fs.clone(),
+            store,
+            selected_index: None,
+            activation_priority: HarpoonSettings::get_global(cx).panel_activation_priority,
+            _store_subscription: store_subscription,
+        }
+    }
+
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: gpui::AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        workspace.update_in(&mut cx, |workspace, window, cx| {
+            cx.new(|cx| Self::new(workspace, window, cx))
+        })
+    }
+
+    fn select_entry(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(index);
+        cx.notify();
+    }
+
+    fn jump_to_entry(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(mark) = self.store.read(cx).marks(cx).get(index).cloned() else {
+            return;
+        };
+        self.store
+            .update(cx, |store, cx| store.record_jump(mark.id, cx));
+        self.workspace
+            .update_in(cx, |workspace, window, cx| {
+                open_mark(workspace, mark.target, mark.cursor, window, cx);
+            })
+            .log_err();
+    }
+
+    fn move_mark(&mut self, index: usize, offset: isize, cx: &mut Context<Self>) {
+        let marks = self.store.read(cx).marks(cx).to_vec();
+        let Some(other_index) = index.checked_add_signed(offset) else {
+            return;
+        };
+        let (Some(mark), Some(other_mark)) = (marks.get(index), marks.get(other_index)) else {
+            return;
+        };
+        let (first, second) = (mark.id, other_mark.id);
+        self.store.update(cx, |store, cx| store.swap_marks(first, second, cx));
+        if self.selected_index == Some(index) {
+            self.selected_index = Some(other_index);
+        }
+    }
+
+    fn remove_mark(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(mark) = self.store.read(cx).marks(cx).get(index).cloned() else {
+            return;
+        };
+        self.store.update(cx, |store, cx| store.remove_mark(mark.id, cx));
+        if self.selected_index == Some(index) {
+            self.selected_index = None;
+        }
+    }
+}
+
+impl EventEmitter<PanelEvent> for HarpoonPanel {}
+
+impl Focusable for HarpoonPanel {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        cx.focus_handle()
+    }
+}
+
+impl Panel for HarpoonPanel {
+    fn persistent_name() -> &'static str {
+        "Harpoon Panel"
+    }
+
+    fn panel_key() -> &'static str {
+        "HarpoonPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        HarpoonSettings::get_global(cx).panel_dock.into()
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.harpoon.get_or_insert_default().panel_dock = Some(position.into())
+        });
+    }
+
+    fn default_size(&self, window: &Window, cx: &App) -> Pixels {
+        let settings = HarpoonSettings::get_global(cx);
+        match self.position(window, cx) {
+            DockPosition::Left | DockPosition::Right => settings.panel_default_width,
+            DockPosition::Bottom => settings.panel_default_height,
+        }
+    }
+
+    fn icon(&self, _: &Window, _: &App) -> Option<IconName> {
+        Some(IconName::Bookmark)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Harpoon Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleHarpoonPanel)
+    }
+
+    fn starts_open(&self, _: &Window, cx: &App) -> bool {
+        HarpoonSettings::get_global(cx).panel_starts_open
+    }
+
+    fn activation_priority(&self) -> u32 {
+        self.activation_priority
+    }
+}
+
+impl Render for HarpoonPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let marks = self.store.read(cx).marks(cx).to_vec();
+        let content = if marks.is_empty() {
+            v_flex()
+                .child(Label::new("No marks yet.").color(Color::Muted))
+                .into_any_element()
+        } else {
+            let last_index = marks.len() - 1;
+            let mut list = v_flex().id("harpoon-panel-entries").flex_1().overflow_y_scroll().gap_1();
+            for (index, mark) in marks.iter().enumerate() {
+                let icon = match mark.target {
+                    HarpoonMarkTarget::File(_) => IconName::File,
+                    HarpoonMarkTarget::Terminal(_) => IconName::Terminal,
+                };
+                list = list.child(
+                    ListItem::new(("harpoon-panel-entry", index))
+                        .spacing(ListItemSpacing::Sparse)
+                        .toggle_state(self.selected_index == Some(index))
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Label::new((index + 1).to_string())
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                )
+                                .child(Icon::new(icon).size(IconSize::Small).color(Color::Muted))
+                                .child(Label::new(mark.target.display_label())),
+                        )
+                        .end_slot(
+                            h_flex()
+                                .gap_1()
+                                .when(index > 0, |this| {
+                                    this.child(
+                                        IconButton::new(("move-up", index), IconName::ChevronUp)
+                                            .icon_size(IconSize::Small)
+                                            .tooltip(Tooltip::text("Move Up"))
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.move_mark(index, -1, cx);
+                                            })),
+                                    )
+                                })
+                                .when(index < last_index, |this| {
+                                    this.child(
+                                        IconButton::new(("move-down", index), IconName::ChevronDown)
+                                            .icon_size(IconSize::Small)
+                                            .tooltip(Tooltip::text("Move Down"))
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.move_mark(index, 1, cx);
+                                            })),
+                                    )
+                                })
+                                .child(
+                                    IconButton::new(("remove-mark", index), IconName::Close)
+                                        .icon_size(IconSize::Small)
+                                        .tooltip(Tooltip::text("Remove Mark"))
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.remove_mark(index, cx);
+                                        })),
+                                ),
+                        )
+                        .on_click(cx.listener(move |this, event: &gpui::ClickEvent, _, cx| {
+                            if event.click_count() > 1 {
+                                this.jump_to_entry(index, cx);
+                            } else {
+                                this.select_entry(index, cx);
+                            }
+                        })),
+                );
+            }
+            list.into_any_element()
+        };
+
+        v_flex().size_full().p_2().child(content)
+    }
+}
@@ -0,0 +1,461 @@
+#[cfg(test)]
+mod harpoon_picker_tests;
+
+use std::sync::Arc;
+
+use fuzzy::StringMatchCandidate;
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, KeyContext, Render,
+    Styled, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use settings::Settings as _;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use crate::{
+    HarpoonMark, HarpoonMarkTarget, HarpoonSettings, MoveSelectedMarkDown, MoveSelectedMarkUp,
+    PeekSelectedMark, RemoveSelectedMark, ToggleHarpoonPicker, get_or_create_harpoon_store,
+    harpoon_settings::HarpoonPickerSort,
+};
+
+/// The height a single harpoon match row occupies in the picker, used to
+/// translate `HarpoonSettings::picker_max_rows` into a max height.
+const ROW_HEIGHT_REMS: f32 = 1.75;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleHarpoonPicker, window, cx| {
+            HarpoonPicker::toggle(workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Clone)]
+struct HarpoonMatch {
+    mark: HarpoonMark,
+    /// This mark's index in `HarpoonStore::marks`, i.e. the slot
+    /// `JumpToSlot` would jump to. Shown alongside the display path since
+    /// `HarpoonSettings::picker_sort` can list marks out of slot order.
+    slot: usize,
+    display_path: Arc<str>,
+    positions: Vec<usize>,
+}
+
+/// A single row in the picker's match list. `Empty` rows are only produced
+/// when `HarpoonSettings::show_empty_slots` is on, to make the slot layout
+/// visible; they can't be selected or confirmed.
+#[derive(Clone)]
+enum HarpoonPickerEntry {
+    Occupied(HarpoonMatch),
+    Empty,
+}
+
+pub struct HarpoonPickerDelegate {
+    store: Entity<crate::HarpoonStore>,
+    workspace: WeakEntity<Workspace>,
+    matches: Vec<HarpoonPickerEntry>,
+    selected_index: usize,
+    /// Whether `PeekSelectedMark` has been invoked since the picker opened,
+    /// so subsequent selection changes keep peeking at the new selection
+    /// instead of requiring the key to be pressed again each time.
+    peeking: bool,
+}
+
+pub struct HarpoonPicker {
+    picker: Entity<Picker<HarpoonPickerDelegate>>,
+    width: gpui::Rems,
+}
+
+impl HarpoonPicker {
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let store = get_or_create_harpoon_store(workspace.project(), cx);
+        let weak_workspace = workspace.weak_handle();
+        let settings = HarpoonSettings::get_global(cx);
+        let picker_width = settings.picker_width;
+        let picker_max_height = gpui::Rems(settings.picker_max_rows as f32 * ROW_HEIGHT_REMS);
+        workspace.toggle_modal(window, cx, |window, cx| {
+            let delegate = HarpoonPickerDelegate {
+                store,
+                workspace: weak_workspace,
+                matches: Vec::new(),
+                selected_index: 0,
+                peeking: false,
+            };
+            let picker = cx.new(|cx| {
+                Picker::uniform_list(delegate, window, cx)
+                    .initial_width(picker_width)
+                    .max_height(picker_max_height)
+            });
+            Self {
+                picker,
+                width: picker_width,
+            }
+        });
+    }
+
+    /// Marks this picker's focus subtree with the `HarpoonPicker` context, so
+    /// workspace-level bindings that should be suppressed while the picker
+    /// has focus (like [`crate::JumpToSlot`]) can exclude it explicitly.
+    fn key_context(&self) -> KeyContext {
+        let mut key_context = KeyContext::new_with_defaults();
+        key_context.add("HarpoonPicker");
+        key_context
+    }
+}
+
+impl HarpoonPickerDelegate {
+    /// Opens the selected file mark in a transient preview tab that's
+    /// replaced as the selection changes and never pinned, unlike
+    /// [`Self::confirm`]. Terminal marks have no preview equivalent, so
+    /// selecting one leaves whatever's currently open untouched.
+    fn peek_selected(&mut self, cx: &mut Context<Picker<Self>>) {
+        let Some(HarpoonPickerEntry::Occupied(entry)) = self.matches.get(self.selected_index)
+        else {
+            return;
+        };
+        self.peeking = true;
+        let HarpoonMarkTarget::File(project_path) = entry.mark.target.clone() else {
+            return;
+        };
+        self.workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace
+                    .open_path_preview(project_path, None, false, true, true, window, cx)
+                    .detach_and_log_err(cx);
+            })
+            .log_err();
+    }
+
+    /// Jumps straight to the mark in `slot`, bypassing `confirm`'s reliance
+    /// on `selected_index`. Used by the `HarpoonPicker`-scoped binding of
+    /// [`crate::JumpToSlot`], which lets a slot be jumped to by number
+    /// without first navigating the picker to select it.
+    fn jump_to_slot(&mut self, slot: usize, cx: &mut Context<Picker<Self>>) {
+        let Some(mark) = self.store.read(cx).marks(cx).get(slot).cloned() else {
+            return;
+        };
+        let mark_id = mark.id;
+        self.store
+            .update(cx, |store, cx| store.record_jump(mark_id, cx));
+        self.workspace
+            .update_in(cx, |workspace, window, cx| {
+                crate::open_mark(workspace, mark.target, mark.cursor, window, cx);
+            })
+            .log_err();
+        cx.emit(DismissEvent);
+    }
+
+    /// The currently selected match, if it's an occupied slot.
+    fn selected_mark(&self) -> Option<&HarpoonMatch> {
+        match self.matches.get(self.selected_index) {
+            Some(HarpoonPickerEntry::Occupied(entry)) => Some(entry),
+            _ => None,
+        }
+    }
+
+    /// Removes the currently selected mark, bound to
+    /// [`crate::RemoveSelectedMark`] inside the `HarpoonPicker` context.
+    /// Callers are responsible for refreshing the picker's matches
+    /// afterwards.
+    fn remove_selected_mark(&mut self, cx: &mut Context<Picker<Self>>) {
+        let Some(mark_id) = self.selected_mark().map(|entry| entry.mark.id) else {
+            return;
+        };
+        self.store
+            .update(cx, |store, cx| store.remove_mark(mark_id, cx));
+    }
+
+    /// Swaps the currently selected mark's slot with the one `offset` away
+    /// from it, bound to [`crate::MoveSelectedMarkUp`]/
+    /// [`crate::MoveSelectedMarkDown`] inside the `HarpoonPicker` context.
+    /// Callers are responsible for refreshing the picker's matches
+    /// afterwards.
+    fn move_selected_mark(&mut self, offset: isize, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.selected_mark() else {
+            return;
+        };
+        let Some(other_slot) = entry.slot.checked_add_signed(offset) else {
+            return;
+        };
+        let marks = self.store.read(cx).marks(cx).to_vec();
+        let Some(other_mark) = marks.get(other_slot) else {
+            return;
+        };
+        let (first, second) = (entry.mark.id, other_mark.id);
+        self.store
+            .update(cx, |store, cx| store.swap_marks(first, second, cx));
+    }
+}
+
+impl PickerDelegate for HarpoonPickerDelegate {
+    type ListItem = ListItem;
+
+    fn name() -> &'static str {
+        "harpoon picker"
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Jump to a mark…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+        if self.peeking {
+            self.peek_selected(cx);
+        }
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let marks = self.store.read(cx).marks(cx).to_vec();
+        let settings = HarpoonSettings::get_global(cx);
+        let show_empty_slots = settings.show_empty_slots;
+        let max_slots = settings.max_slots;
+        let picker_sort = settings.picker_sort;
+        cx.spawn(async move |picker, cx| {
+            let mut candidates: Vec<HarpoonMatch> = marks
+                .into_iter()
+                .enumerate()
+                .map(|(slot, mark)| {
+                    let display_path: Arc<str> = mark.target.display_label().into();
+                    HarpoonMatch {
+                        mark,
+                        slot,
+                        display_path,
+                        positions: Vec::new(),
+                    }
+                })
+                .collect();
+            match picker_sort {
+                HarpoonPickerSort::Slot => {}
+                HarpoonPickerSort::Recent => {
+                    candidates.sort_by(|a, b| b.mark.last_jumped.cmp(&a.mark.last_jumped));
+                }
+                HarpoonPickerSort::Alpha => {
+                    candidates.sort_by(|a, b| a.display_path.cmp(&b.display_path));
+                }
+            }
+
+            let matches = if query.is_empty() {
+                let mut entries: Vec<HarpoonPickerEntry> = candidates
+                    .into_iter()
+                    .map(HarpoonPickerEntry::Occupied)
+                    .collect();
+                if show_empty_slots {
+                    entries.resize_with(entries.len().max(max_slots), || HarpoonPickerEntry::Empty);
+                }
+                entries
+            } else {
+                let string_candidates = candidates
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, candidate)| StringMatchCandidate::new(ix, &candidate.display_path))
+                    .collect::<Vec<_>>();
+                let results = fuzzy::match_strings(
+                    &string_candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await;
+                results
+                    .into_iter()
+                    .map(|result| {
+                        HarpoonPickerEntry::Occupied(HarpoonMatch {
+                            positions: result.positions,
+                            ..candidates[result.candidate_id].clone()
+                        })
+                    })
+                    .collect()
+            };
+
+            picker
+                .update(cx, |picker, _| {
+                    picker.delegate.selected_index = 0;
+                    picker.delegate.matches = matches;
+                })
+                .ok();
+        })
+    }
+
+    fn can_select(
+        &self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> bool {
+        matches!(self.matches.get(ix), Some(HarpoonPickerEntry::Occupied(_)))
+    }
+
+    fn confirm(&mut self, _secondary: bool, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(HarpoonPickerEntry::Occupied(entry)) = self.matches.get(self.selected_index)
+        else {
+            return;
+        };
+        let mark_id = entry.mark.id;
+        let target = entry.mark.target.clone();
+        let cursor = entry.mark.cursor;
+        self.store
+            .update(cx, |store, cx| store.record_jump(mark_id, cx));
+        self.workspace
+            .update_in(cx, |workspace, window, cx| {
+                crate::open_mark(workspace, target, cursor, window, cx);
+            })
+            .log_err();
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    /// `Picker::render_element` already wraps every row in a click handler
+    /// that calls `can_select`/`set_selected_index`/`confirm` for us, and
+    /// `ListItem` already shows a hover highlight for selectable rows, so
+    /// this delegate doesn't need its own mouse handling to make rows
+    /// clickable.
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = &self.matches[ix];
+        let label = match entry {
+            HarpoonPickerEntry::Occupied(entry) => h_flex()
+                .gap_2()
+                .child(
+                    Label::new((entry.slot + 1).to_string())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .child(HighlightedLabel::new(
+                    entry.display_path.to_string(),
+                    entry.positions.clone(),
+                ))
+                .when(HarpoonSettings::get_global(cx).show_jump_counts, |this| {
+                    this.child(
+                        Label::new(format!("{}×", entry.mark.jump_count))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                })
+                .when_some(entry.mark.comment.clone(), |this, comment| {
+                    this.child(
+                        Label::new(comment)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                })
+                .into_any_element(),
+            HarpoonPickerEntry::Empty => HighlightedLabel::new(
+                format!("{} — empty —", ix + 1),
+                Vec::new(),
+            )
+            .color(Color::Muted)
+            .into_any_element(),
+        };
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .disabled(matches!(entry, HarpoonPickerEntry::Empty))
+                .child(label),
+        )
+    }
+}
+
+impl EventEmitter<DismissEvent> for HarpoonPicker {}
+
+impl ModalView for HarpoonPicker {}
+
+impl Focusable for HarpoonPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for HarpoonPicker {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let list_name = self
+            .picker
+            .read(cx)
+            .delegate
+            .store
+            .read(cx)
+            .snapshot(cx)
+            .active_list_name
+            .unwrap_or_else(|| "Harpoon".into());
+        v_flex()
+            .key_context(self.key_context())
+            .w(self.width)
+            .child(
+                h_flex()
+                    .gap_1()
+                    .px_2()
+                    .py_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Icon::new(IconName::Bookmark)
+                            .color(Color::Muted)
+                            .size(IconSize::XSmall),
+                    )
+                    .child(Label::new(list_name).size(LabelSize::Small).color(Color::Muted)),
+            )
+            .on_action(cx.listener(|this, _: &PeekSelectedMark, _window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.peek_selected(cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &RemoveSelectedMark, _window, cx| {
+                this.picker.update_in(cx, |picker, window, cx| {
+                    picker.delegate.remove_selected_mark(cx);
+                    picker.refresh(window, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &MoveSelectedMarkUp, _window, cx| {
+                this.picker.update_in(cx, |picker, window, cx| {
+                    picker.delegate.move_selected_mark(-1, cx);
+                    picker.refresh(window, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, _: &MoveSelectedMarkDown, _window, cx| {
+                this.picker.update_in(cx, |picker, window, cx| {
+                    picker.delegate.move_selected_mark(1, cx);
+                    picker.refresh(window, cx);
+                });
+            }))
+            .on_action(cx.listener(|this, jump: &crate::JumpToSlot, _window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.jump_to_slot(jump.0, cx);
+                });
+            }))
+            .child(self.picker.clone())
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use super::*;
+use gpui::TestAppContext;
+use project::Project;
+use serde_json::json;
+use util::path;
+use workspace::{AppState, MultiWorkspace};
+
+#[ctor::ctor(unsafe)]
+fn init_logger() {
+    zlog::init_test();
+}
+
+#[gpui::test]
+async fn test_toggle_and_dismiss_with_no_marks(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "contents" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+    cx.dispatch_action(ToggleHarpoonPicker);
+    let picker = workspace.update(cx, |workspace, cx| {
+        workspace
+            .active_modal::<HarpoonPicker>(cx)
+            .expect("harpoon picker is not open")
+            .read(cx)
+            .picker
+            .clone()
+    });
+    picker.update(cx, |picker, _| {
+        assert_eq!(picker.delegate.matches.len(), 0);
+    });
+
+    cx.dispatch_action(menu::Cancel);
+    workspace.update(cx, |workspace, cx| {
+        assert!(workspace.active_modal::<HarpoonPicker>(cx).is_none());
+    });
+}
+
+fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+    cx.update(|cx| {
+        let state = AppState::test(cx);
+        theme_settings::init(theme::LoadThemes::JustBase, cx);
+        crate::init(cx);
+        editor::init(cx);
+        state
+    })
+}
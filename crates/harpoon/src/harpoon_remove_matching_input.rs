@@ -0,0 +1,137 @@
+use editor::{Editor, EditorEvent};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription,
+    WeakEntity, Window,
+};
+use theme::ActiveTheme;
+use ui::prelude::*;
+use util::paths::{PathMatcher, PathStyle};
+use workspace::{ModalView, Toast, Workspace, notifications::NotificationId};
+
+use crate::{HarpoonStore, RemoveMatching, get_or_create_harpoon_store};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &RemoveMatching, window, cx| {
+            HarpoonRemoveMatchingInput::toggle(workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// A minimal modal that bulk-removes every mark whose path matches a typed
+/// glob or prefix, for cleaning up after a work session without removing
+/// marks one by one in the picker. A plain path like `src/old_module/`
+/// matches as a prefix; wildcards like `src/old_module/**` are matched as a
+/// glob, per [`PathMatcher`].
+pub struct HarpoonRemoveMatchingInput {
+    store: Entity<HarpoonStore>,
+    workspace: WeakEntity<Workspace>,
+    input_editor: Entity<Editor>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl HarpoonRemoveMatchingInput {
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let store = get_or_create_harpoon_store(workspace.project(), cx);
+        let weak_workspace = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(store, weak_workspace, window, cx)
+        });
+    }
+
+    fn new(
+        store: Entity<HarpoonStore>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let input_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Glob or path prefix…", window, cx);
+            editor
+        });
+        let input_editor_change =
+            cx.subscribe_in(&input_editor, window, Self::on_input_editor_event);
+        Self {
+            store,
+            workspace,
+            input_editor,
+            _subscriptions: vec![input_editor_change],
+        }
+    }
+
+    fn on_input_editor_event(
+        &mut self,
+        _: &Entity<Editor>,
+        event: &EditorEvent,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if matches!(event, EditorEvent::Blurred) {
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let typed_pattern = self.input_editor.read(cx).text(cx);
+        let typed_pattern = typed_pattern.trim();
+        if let Ok(matcher) = PathMatcher::new([typed_pattern], PathStyle::local()) {
+            let removed = self
+                .store
+                .update(cx, |store, cx| store.remove_matching(&matcher, cx));
+            let message = format!(
+                "Removed {removed} mark{} matching \"{typed_pattern}\"",
+                if removed == 1 { "" } else { "s" }
+            );
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    workspace.show_toast(
+                        Toast::new(NotificationId::unique::<RemoveMatching>(), message),
+                        cx,
+                    );
+                })
+                .ok();
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for HarpoonRemoveMatchingInput {}
+
+impl ModalView for HarpoonRemoveMatchingInput {}
+
+impl Focusable for HarpoonRemoveMatchingInput {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input_editor.focus_handle(cx)
+    }
+}
+
+impl Render for HarpoonRemoveMatchingInput {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("HarpoonRemoveMatchingInput")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(self.input_editor.clone()),
+            )
+            .child(
+                h_flex().px_2().py_1().gap_1().child(
+                    Label::new("Remove marks matching a glob or prefix, Enter to confirm")
+                        .color(Color::Muted),
+                ),
+            )
+    }
+}
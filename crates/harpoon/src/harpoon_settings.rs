@@ -0,0 +1,204 @@
+use gpui::{Pixels, Rems};
+pub use settings::DockPosition;
+use settings::{RegisterSetting, Settings, SettingsContent};
+
+/// The harpoon picker's width and row count are clamped to these bounds so a
+/// stray setting can't render the picker unusably tiny or larger than most
+/// screens.
+const MIN_PICKER_WIDTH_REMS: f32 = 20.0;
+const MAX_PICKER_WIDTH_REMS: f32 = 80.0;
+const MIN_PICKER_MAX_ROWS: usize = 3;
+const MAX_PICKER_MAX_ROWS: usize = 30;
+
+/// Settings for the harpoon marking workflow.
+#[derive(Debug, Clone, RegisterSetting)]
+pub struct HarpoonSettings {
+    /// Whether to seed a project's marks from a `.harpoon.json` file committed
+    /// at the worktree root, when no marks have been saved for it yet.
+    pub seed_from_project_file: bool,
+    /// How long, in milliseconds, a second `Mark` press within the window
+    /// after the first is treated as a double-tap that opens the picker
+    /// instead of marking again.
+    pub double_tap_window_ms: u64,
+    /// The maximum number of marks a project can hold at once. New marks
+    /// beyond this limit are not added.
+    pub max_slots: usize,
+    /// The width of the harpoon picker.
+    pub picker_width: Rems,
+    /// The maximum number of marks visible in the harpoon picker before it
+    /// scrolls.
+    pub picker_max_rows: usize,
+    /// How long, in milliseconds, a `ClearAllMarks` can be undone via
+    /// `RestoreMarks` before the cleared marks are discarded for good.
+    pub clear_undo_window_ms: u64,
+    /// Whether the harpoon picker shows every slot up to `max_slots`,
+    /// rendering unoccupied ones as placeholder rows, instead of only the
+    /// occupied marks.
+    pub show_empty_slots: bool,
+    /// Whether to reopen each current mark as a background tab once a
+    /// project's marks have finished loading, so a project opens back up
+    /// anchored on its curated marks instead of raw recency.
+    pub restore_marks_as_tabs: bool,
+    /// The order in which marks are listed in the harpoon picker. Slot
+    /// numbers shown in the picker always reflect the true slot regardless of
+    /// this setting.
+    pub picker_sort: HarpoonPickerSort,
+    /// Whether jumping to a mark briefly flashes the line the cursor lands
+    /// on, mirroring jump-to-definition. The view is centered on that line
+    /// regardless of this setting, unless `restore_scroll_position` restores
+    /// the mark's recorded scroll position instead.
+    pub flash_on_jump: bool,
+    /// Whether `OpenAll` opens each mark after the first in its own split,
+    /// instead of as a background tab.
+    pub open_all_in_splits: bool,
+    /// Automatically marks the first N distinct files opened in a project
+    /// session, up to `max_slots`. `0` disables this entirely.
+    pub auto_mark_first: usize,
+    /// Whether a bookmark icon is shown after the title of a tab whose file
+    /// is currently marked.
+    pub show_marked_indicator: bool,
+    /// Whether `SetSlot` prompts for confirmation before replacing a slot
+    /// that already holds a mark.
+    pub confirm_overwrite: bool,
+    /// The position of the harpoon panel.
+    pub panel_dock: DockPosition,
+    /// The harpoon panel's default width.
+    pub panel_default_width: Pixels,
+    /// The harpoon panel's default height when docked at the bottom.
+    pub panel_default_height: Pixels,
+    /// Whether the harpoon panel should open on startup.
+    pub panel_starts_open: bool,
+    /// Where the harpoon panel's icon ranks among other panel icons in the
+    /// status bar.
+    pub panel_activation_priority: u32,
+    /// Whether marks are shared across the whole project or kept separate
+    /// per worktree.
+    pub scope: HarpoonScope,
+    /// Whether jumping to the slot that's already the active file instead
+    /// switches to the previously active file (via `workspace::AlternateFile`),
+    /// so a single `JumpToSlot` binding doubles as a toggle between two files.
+    pub bounce_on_repeat: bool,
+    /// Whether jumping to a mark restores the scroll position it was
+    /// captured at, instead of centering the view on the restored cursor.
+    /// Falls back to centering when the mark's recorded position no longer
+    /// resolves in the current buffer.
+    pub restore_scroll_position: bool,
+    /// Whether the harpoon picker shows each mark's jump count for the
+    /// current session, so marks that aren't actually getting used can be
+    /// spotted and pruned. Purely local: the count lives only in memory and
+    /// is never persisted or reported anywhere.
+    pub show_jump_counts: bool,
+    /// Whether marking a file prompts for a short one-line note, stored as
+    /// the new mark's comment, turning it into a lightweight TODO anchor.
+    /// Off by default so the fast path stays fast.
+    pub prompt_on_mark: bool,
+    /// How `NormalizeSlots` reorders and compacts occupied marks.
+    pub normalize_slots_order: HarpoonNormalizeSlotsOrder,
+}
+
+/// Whether [`crate::HarpoonStore`] keeps one mark set for the whole project
+/// or a separate one per worktree.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum HarpoonScope {
+    /// One shared mark set for the whole project.
+    #[default]
+    Project,
+    /// A separate mark set per worktree, following the active file.
+    Worktree,
+}
+
+impl From<settings::HarpoonScopeContent> for HarpoonScope {
+    fn from(content: settings::HarpoonScopeContent) -> Self {
+        match content {
+            settings::HarpoonScopeContent::Project => HarpoonScope::Project,
+            settings::HarpoonScopeContent::Worktree => HarpoonScope::Worktree,
+        }
+    }
+}
+
+/// The order in which [`crate::HarpoonPicker`] lists marks.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum HarpoonPickerSort {
+    /// List marks in slot order.
+    #[default]
+    Slot,
+    /// List the most recently jumped-to mark first.
+    Recent,
+    /// List marks alphabetically by display path.
+    Alpha,
+}
+
+impl From<settings::HarpoonPickerSortContent> for HarpoonPickerSort {
+    fn from(content: settings::HarpoonPickerSortContent) -> Self {
+        match content {
+            settings::HarpoonPickerSortContent::Slot => HarpoonPickerSort::Slot,
+            settings::HarpoonPickerSortContent::Recent => HarpoonPickerSort::Recent,
+            settings::HarpoonPickerSortContent::Alpha => HarpoonPickerSort::Alpha,
+        }
+    }
+}
+
+/// The order `harpoon::NormalizeSlots` reorders occupied marks into before
+/// compacting them into slots `1..N`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum HarpoonNormalizeSlotsOrder {
+    /// Sort marks alphabetically by display path.
+    #[default]
+    Path,
+    /// Sort marks by the order they were originally created in.
+    MarkOrder,
+}
+
+impl From<settings::HarpoonNormalizeSlotsOrderContent> for HarpoonNormalizeSlotsOrder {
+    fn from(content: settings::HarpoonNormalizeSlotsOrderContent) -> Self {
+        match content {
+            settings::HarpoonNormalizeSlotsOrderContent::Path => {
+                HarpoonNormalizeSlotsOrder::Path
+            }
+            settings::HarpoonNormalizeSlotsOrderContent::MarkOrder => {
+                HarpoonNormalizeSlotsOrder::MarkOrder
+            }
+        }
+    }
+}
+
+impl Settings for HarpoonSettings {
+    fn from_settings(content: &SettingsContent) -> Self {
+        let harpoon = content.harpoon.as_ref().unwrap();
+        Self {
+            seed_from_project_file: harpoon.seed_from_project_file.unwrap(),
+            double_tap_window_ms: harpoon.double_tap_window_ms.unwrap(),
+            max_slots: harpoon.max_slots.unwrap(),
+            picker_width: Rems(
+                harpoon
+                    .picker_width
+                    .unwrap()
+                    .clamp(MIN_PICKER_WIDTH_REMS, MAX_PICKER_WIDTH_REMS),
+            ),
+            picker_max_rows: harpoon
+                .picker_max_rows
+                .unwrap()
+                .clamp(MIN_PICKER_MAX_ROWS, MAX_PICKER_MAX_ROWS),
+            clear_undo_window_ms: harpoon.clear_undo_window_ms.unwrap(),
+            show_empty_slots: harpoon.show_empty_slots.unwrap(),
+            restore_marks_as_tabs: harpoon.restore_marks_as_tabs.unwrap(),
+            picker_sort: harpoon.picker_sort.unwrap().into(),
+            flash_on_jump: harpoon.flash_on_jump.unwrap(),
+            open_all_in_splits: harpoon.open_all_in_splits.unwrap(),
+            auto_mark_first: harpoon.auto_mark_first.unwrap(),
+            show_marked_indicator: harpoon.show_marked_indicator.unwrap(),
+            confirm_overwrite: harpoon.confirm_overwrite.unwrap(),
+            panel_dock: harpoon.panel_dock.unwrap(),
+            panel_default_width: harpoon.panel_default_width.map(gpui::px).unwrap(),
+            panel_default_height: harpoon.panel_default_height.map(gpui::px).unwrap(),
+            panel_starts_open: harpoon.panel_starts_open.unwrap(),
+            panel_activation_priority: harpoon.panel_activation_priority.unwrap(),
+            scope: harpoon.scope.unwrap().into(),
+            bounce_on_repeat: harpoon.bounce_on_repeat.unwrap(),
+            restore_scroll_position: harpoon.restore_scroll_position.unwrap(),
+            show_jump_counts: harpoon.show_jump_counts.unwrap(),
+            prompt_on_mark: harpoon.prompt_on_mark.unwrap(),
+            normalize_slots_order: harpoon.normalize_slots_order.unwrap().into(),
+        }
+    }
+}
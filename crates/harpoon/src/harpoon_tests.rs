@@ -0,0 +1,1288 @@
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use editor::Editor;
+use gpui::{Entity, TestAppContext};
+use multi_buffer::MultiBufferOffset;
+use project::{Project, WorktreeId};
+use serde_json::json;
+use util::{path, rel_path::rel_path};
+use workspace::{AppState, MultiWorkspace, Workspace};
+
+use super::*;
+
+#[ctor::ctor(unsafe)]
+fn init_logger() {
+    zlog::init_test();
+}
+
+#[gpui::test]
+async fn test_mark_without_project_path_shows_toast(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "contents" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+    let buffer = project.update(cx, |project, cx| {
+        project.create_local_buffer("", None, false, cx)
+    });
+    workspace.update_in(cx, |workspace, window, cx| {
+        let editor = cx.new(|cx| Editor::for_buffer(buffer, Some(project.clone()), window, cx));
+        workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+    });
+
+    cx.dispatch_action(Mark);
+
+    workspace.update(cx, |workspace, _cx| {
+        assert_eq!(
+            workspace.notification_ids().len(),
+            1,
+            "marking a buffer with no project path should show a toast instead of marking"
+        );
+    });
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        assert_eq!(store.read(cx).marks(cx).len(), 0);
+    });
+}
+
+#[gpui::test]
+async fn test_mark_with_multiple_cursors_records_primary(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one\ntwo\nthree\nfour\n" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, rel_path("1.txt")), cx)
+        })
+        .await
+        .unwrap();
+    workspace.update_in(cx, |workspace, window, cx| {
+        let editor = cx.new(|cx| Editor::for_buffer(buffer, Some(project.clone()), window, cx));
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(Default::default(), window, cx, |selections| {
+                selections.select_ranges([
+                    MultiBufferOffset(0)..MultiBufferOffset(0),
+                    MultiBufferOffset(14)..MultiBufferOffset(14),
+                ])
+            });
+        });
+        workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+    });
+
+    cx.dispatch_action(Mark);
+    let double_tap_window_ms = cx.update(|cx| HarpoonSettings::get_global(cx).double_tap_window_ms);
+    cx.executor()
+        .advance_clock(Duration::from_millis(double_tap_window_ms + 1));
+    cx.run_until_parked();
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        let marks = store.read(cx).marks(cx);
+        assert_eq!(marks.len(), 1);
+        assert_eq!(
+            marks[0].cursor,
+            Some(HarpoonCursor {
+                head: Point::new(3, 0),
+                selection: None,
+                scroll_top: Point::new(0, 0),
+            }),
+            "mark should record the newest (last-added) selection's head, not the first"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_mark_with_nonempty_selection_records_range(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one\ntwo\nthree\nfour\n" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, rel_path("1.txt")), cx)
+        })
+        .await
+        .unwrap();
+    workspace.update_in(cx, |workspace, window, cx| {
+        let editor = cx.new(|cx| Editor::for_buffer(buffer, Some(project.clone()), window, cx));
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(Default::default(), window, cx, |selections| {
+                selections.select_ranges([MultiBufferOffset(4)..MultiBufferOffset(12)])
+            });
+        });
+        workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+    });
+
+    cx.dispatch_action(Mark);
+    let double_tap_window_ms = cx.update(|cx| HarpoonSettings::get_global(cx).double_tap_window_ms);
+    cx.executor()
+        .advance_clock(Duration::from_millis(double_tap_window_ms + 1));
+    cx.run_until_parked();
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        let marks = store.read(cx).marks(cx);
+        assert_eq!(marks.len(), 1);
+        assert_eq!(
+            marks[0].cursor,
+            Some(HarpoonCursor {
+                head: Point::new(2, 4),
+                selection: Some(Point::new(1, 0)..Point::new(2, 4)),
+                scroll_top: Point::new(0, 0),
+            }),
+            "mark should record the non-empty selection's range, not just its head"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_mark_terminal_dedups_by_working_directory(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "contents" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            let cwd: Arc<Path> = Arc::from(Path::new("/tmp/work"));
+            assert!(store.mark_terminal(cwd.clone(), cx));
+            assert!(
+                !store.mark_terminal(cwd.clone(), cx),
+                "marking the same working directory twice shouldn't add a duplicate"
+            );
+            assert_eq!(store.marks(cx).len(), 1);
+            assert_eq!(store.marks(cx)[0].target, HarpoonMarkTarget::Terminal(cwd));
+        });
+    });
+}
+
+#[gpui::test]
+async fn test_snapshot_reports_marks_with_slots_and_max_slots(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one", "2.txt": "two" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    let snapshot = cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("2.txt")).into(), None, cx).unwrap();
+        });
+        store.read(cx).snapshot(cx)
+    });
+
+    assert_eq!(snapshot.marks.len(), 2);
+    assert_eq!(snapshot.marks[0].slot, 0);
+    assert_eq!(
+        snapshot.marks[0].target,
+        HarpoonMarkTarget::File((worktree_id, rel_path("1.txt")).into())
+    );
+    assert_eq!(snapshot.marks[1].slot, 1);
+    assert_eq!(snapshot.active_list_name, None);
+    assert_eq!(
+        snapshot.max_slots,
+        cx.update(|cx| HarpoonSettings::get_global(cx).max_slots)
+    );
+}
+
+#[gpui::test]
+async fn test_record_jump_tallies_jump_count_per_mark(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one", "2.txt": "two" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        let (first_id, second_id) = store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("2.txt")).into(), None, cx).unwrap();
+            let marks = store.marks(cx);
+            (marks[0].id, marks[1].id)
+        });
+        store.update(cx, |store, cx| {
+            store.record_jump(first_id, cx);
+            store.record_jump(first_id, cx);
+            store.record_jump(second_id, cx);
+        });
+        let marks = store.read(cx).marks(cx);
+        assert_eq!(marks[0].jump_count, 2);
+        assert_eq!(marks[1].jump_count, 1);
+    });
+}
+
+#[gpui::test]
+async fn test_record_jump_tracks_alternate_target_for_toggle_last_two(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one", "2.txt": "two" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        let (first_id, second_id) = store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("2.txt")).into(), None, cx).unwrap();
+            let marks = store.marks(cx);
+            (marks[0].id, marks[1].id)
+        });
+
+        assert_eq!(store.read(cx).alternate_jump_target(), None);
+
+        store.update(cx, |store, cx| store.record_jump(first_id, cx));
+        assert_eq!(store.read(cx).alternate_jump_target(), None);
+
+        store.update(cx, |store, cx| store.record_jump(second_id, cx));
+        let first_target = store.read(cx).marks(cx)[0].target.clone();
+        assert_eq!(store.read(cx).alternate_jump_target(), Some(first_target));
+
+        // Jumping to the same mark again shouldn't collapse the ring.
+        store.update(cx, |store, cx| store.record_jump(second_id, cx));
+        let first_target = store.read(cx).marks(cx)[0].target.clone();
+        assert_eq!(store.read(cx).alternate_jump_target(), Some(first_target));
+    });
+}
+
+#[gpui::test]
+async fn test_set_comment_updates_and_clears_a_mark_comment(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        let mark_id = store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+            store.marks(cx)[0].id
+        });
+        assert_eq!(store.read(cx).marks(cx)[0].comment, None);
+
+        store.update(cx, |store, cx| {
+            store.set_comment(mark_id, Some("fix the parser bug here".into()), cx);
+        });
+        assert_eq!(
+            store.read(cx).marks(cx)[0].comment.as_deref(),
+            Some("fix the parser bug here")
+        );
+
+        store.update(cx, |store, cx| store.set_comment(mark_id, None, cx));
+        assert_eq!(store.read(cx).marks(cx)[0].comment, None);
+    });
+}
+
+#[gpui::test]
+async fn test_set_comment_persists_across_a_restart(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    // An isolated in-memory database, so this test's reads and writes can't
+    // race other tests sharing the same `/root` worktree path under the
+    // process-wide fallback database.
+    cx.update(|cx| cx.set_global(db::AppDatabase::test_new()));
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        let mark_id = store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+            store.marks(cx)[0].id
+        });
+        store.update(cx, |store, cx| {
+            store.set_comment(mark_id, Some("fix the parser bug here".into()), cx);
+        });
+    });
+    cx.run_until_parked();
+
+    // Construct a fresh `HarpoonStore` directly instead of going through
+    // `get_or_create_harpoon_store`, which would just hand back the existing
+    // in-memory instance from its `GlobalHarpoonStore` cache.
+    let fs = project.read_with(cx, |project, _| project.fs().clone());
+    let reloaded = cx.update(|cx| {
+        cx.new(|cx| {
+            let mut store = HarpoonStore::new(project.clone(), fs, cx);
+            store.load_persisted_marks(cx);
+            store
+        })
+    });
+    cx.update(|cx| {
+        let marks = reloaded.read(cx).marks(cx);
+        assert_eq!(marks.len(), 1);
+        assert_eq!(marks[0].comment.as_deref(), Some("fix the parser bug here"));
+    });
+}
+
+#[gpui::test]
+async fn test_get_or_create_harpoon_store_survives_project_drop_and_recreate(
+    cx: &mut TestAppContext,
+) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    let mark_id = cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+            store.marks(cx)[0].id
+        })
+    });
+
+    let weak_project = project.downgrade();
+    drop(project);
+    cx.run_until_parked();
+    cx.update(|cx| assert!(weak_project.upgrade().is_none()));
+
+    // A fresh project entity over the same worktree simulates the window
+    // reload this is meant to survive: the old project is gone, but the
+    // mark should still be there.
+    let recreated_project =
+        Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&recreated_project, cx);
+        let marks = store.read(cx).marks(cx);
+        assert_eq!(marks.len(), 1);
+        assert_eq!(marks[0].id, mark_id);
+    });
+}
+
+#[gpui::test]
+async fn test_open_all_marks_focuses_first_and_reports_missing_files(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({ "1.txt": "one", "2.txt": "two", "3.txt": "three" }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("2.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("missing.txt")).into(), None, cx).unwrap();
+        });
+    });
+
+    cx.dispatch_action(OpenAll);
+    cx.run_until_parked();
+
+    workspace.update(cx, |workspace, cx| {
+        let open_paths: collections::HashSet<_> =
+            workspace.items(cx).filter_map(|item| item.project_path(cx)).collect();
+        assert_eq!(open_paths.len(), 2);
+        let active_path = workspace.active_item(cx).and_then(|item| item.project_path(cx));
+        assert_eq!(active_path, Some((worktree_id, rel_path("1.txt")).into()));
+        assert_eq!(
+            workspace.notification_ids().len(),
+            1,
+            "a mark whose file no longer exists should be reported via a toast"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_auto_mark_first_marks_only_the_first_n_distinct_files(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({ "1.txt": "one", "2.txt": "two", "3.txt": "three" }),
+        )
+        .await;
+
+    cx.update(|cx| {
+        settings::SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.harpoon.get_or_insert_default().auto_mark_first = Some(2);
+            });
+        });
+    });
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    for file_name in ["1.txt", "2.txt", "1.txt", "3.txt"] {
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_buffer((worktree_id, rel_path(file_name)), cx)
+            })
+            .await
+            .unwrap();
+        workspace.update_in(cx, |workspace, window, cx| {
+            let editor = cx.new(|cx| Editor::for_buffer(buffer, Some(project.clone()), window, cx));
+            workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+        });
+    }
+    cx.run_until_parked();
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        let marks = store.read(cx).marks(cx);
+        assert_eq!(
+            marks.len(),
+            2,
+            "only the first 2 distinct files opened should be auto-marked"
+        );
+        assert_eq!(
+            marks[0].target,
+            HarpoonMarkTarget::File((worktree_id, rel_path("1.txt")).into())
+        );
+        assert_eq!(
+            marks[1].target,
+            HarpoonMarkTarget::File((worktree_id, rel_path("2.txt")).into())
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_is_marked_reflects_current_marks(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one", "2.txt": "two" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let marked_path = ProjectPath::from((worktree_id, rel_path("1.txt")));
+    let unmarked_path = ProjectPath::from((worktree_id, rel_path("2.txt")));
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark(marked_path.clone(), None, cx).unwrap();
+        });
+        assert!(store.read(cx).is_marked(&marked_path, cx));
+        assert!(!store.read(cx).is_marked(&unmarked_path, cx));
+    });
+}
+
+#[gpui::test]
+async fn test_remove_matching_removes_only_matching_paths(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({
+                "old_module": { "a.rs": "a", "b.rs": "b" },
+                "new_module": { "c.rs": "c" },
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let old_a = ProjectPath::from((worktree_id, rel_path("old_module/a.rs")));
+    let old_b = ProjectPath::from((worktree_id, rel_path("old_module/b.rs")));
+    let new_c = ProjectPath::from((worktree_id, rel_path("new_module/c.rs")));
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark(old_a.clone(), None, cx).unwrap();
+            store.mark(old_b.clone(), None, cx).unwrap();
+            store.mark(new_c.clone(), None, cx).unwrap();
+        });
+
+        let matcher = PathMatcher::new(["old_module"], PathStyle::local()).unwrap();
+        let removed = store.update(cx, |store, cx| store.remove_matching(&matcher, cx));
+        assert_eq!(removed, 2);
+        assert!(!store.read(cx).is_marked(&old_a, cx));
+        assert!(!store.read(cx).is_marked(&old_b, cx));
+        assert!(store.read(cx).is_marked(&new_c, cx));
+
+        let removed_again = store.update(cx, |store, cx| store.remove_matching(&matcher, cx));
+        assert_eq!(removed_again, 0, "a second pass with no matches should be a no-op");
+    });
+}
+
+#[gpui::test]
+async fn test_remove_matching_persists_the_removal_across_a_restart(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    // An isolated in-memory database, so this test's reads and writes can't
+    // race other tests sharing the same `/root` worktree path under the
+    // process-wide fallback database.
+    cx.update(|cx| cx.set_global(db::AppDatabase::test_new()));
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({
+                "old_module": { "a.rs": "a" },
+                "new_module": { "c.rs": "c" },
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let new_c = ProjectPath::from((worktree_id, rel_path("new_module/c.rs")));
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store
+                .mark(
+                    ProjectPath::from((worktree_id, rel_path("old_module/a.rs"))),
+                    None,
+                    cx,
+                )
+                .unwrap();
+            store.mark(new_c.clone(), None, cx).unwrap();
+        });
+        let matcher = PathMatcher::new(["old_module"], PathStyle::local()).unwrap();
+        store.update(cx, |store, cx| {
+            assert_eq!(store.remove_matching(&matcher, cx), 1);
+        });
+    });
+    cx.run_until_parked();
+
+    // Construct a fresh `HarpoonStore` directly instead of going through
+    // `get_or_create_harpoon_store`, which would just hand back the existing
+    // in-memory instance from its `GlobalHarpoonStore` cache.
+    let fs = project.read_with(cx, |project, _| project.fs().clone());
+    let reloaded = cx.update(|cx| {
+        cx.new(|cx| {
+            let mut store = HarpoonStore::new(project.clone(), fs, cx);
+            store.load_persisted_marks(cx);
+            store
+        })
+    });
+    cx.update(|cx| {
+        let marks = reloaded.read(cx).marks(cx);
+        assert_eq!(marks.len(), 1, "the removed mark should not come back after a restart");
+        assert_eq!(marks[0].target, HarpoonMarkTarget::File(new_c.clone()));
+    });
+}
+
+#[gpui::test]
+async fn test_set_slot_swaps_an_already_marked_path_into_the_requested_slot(
+    cx: &mut TestAppContext,
+) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one", "2.txt": "two" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let one = ProjectPath::from((worktree_id, rel_path("1.txt")));
+    let two = ProjectPath::from((worktree_id, rel_path("2.txt")));
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark(one.clone(), None, cx).unwrap();
+            store.mark(two.clone(), None, cx).unwrap();
+            store.set_slot(1, one.clone(), cx).unwrap();
+        });
+        let marks = store.read(cx).marks(cx);
+        assert_eq!(
+            marks.len(),
+            2,
+            "setting a slot to an already-marked path should swap it in, \
+             not duplicate or drop the displaced mark"
+        );
+        assert_eq!(
+            store.read(cx).slot_for(&one, cx),
+            Some(1),
+            "the path should actually move into the requested slot"
+        );
+        assert_eq!(
+            store.read(cx).slot_for(&two, cx),
+            Some(0),
+            "the mark displaced from the requested slot should land in the moved path's old slot"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_set_slot_does_not_disturb_marks_past_the_requested_slot(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({ "1.txt": "one", "2.txt": "two", "3.txt": "three" }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let one = ProjectPath::from((worktree_id, rel_path("1.txt")));
+    let two = ProjectPath::from((worktree_id, rel_path("2.txt")));
+    let three = ProjectPath::from((worktree_id, rel_path("3.txt")));
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark(one.clone(), None, cx).unwrap();
+            store.mark(two.clone(), None, cx).unwrap();
+            store.mark(three.clone(), None, cx).unwrap();
+            // `one` is already marked at slot 0; asking for slot 1 should
+            // move it there and displace `two`, without touching `three`.
+            store.set_slot(1, one.clone(), cx).unwrap();
+        });
+        assert_eq!(store.read(cx).marks(cx).len(), 3);
+        assert_eq!(
+            store.read(cx).slot_for(&one, cx),
+            Some(1),
+            "the path should actually move into the requested slot"
+        );
+        assert_eq!(
+            store.read(cx).slot_for(&two, cx),
+            Some(0),
+            "the mark displaced from the requested slot should land in the moved path's old slot"
+        );
+        assert_eq!(
+            store.read(cx).slot_for(&three, cx),
+            Some(2),
+            "a mark past the requested slot should keep its original slot"
+        );
+    });
+}
+
+#[test]
+fn test_seed_file_v0_payload_migrates_cleanly() {
+    let v0_payload = r#"{"marks": [{"path": "notes/a.md"}, {"path": "notes/b.md"}]}"#;
+    let persisted: PersistedHarpoonMarks = serde_json::from_str(v0_payload).unwrap();
+    assert_eq!(persisted.version, 0, "a payload with no version field should parse as v0");
+
+    let migrated = persisted.migrate();
+    assert_eq!(migrated.version, PERSISTED_MARKS_VERSION);
+    assert_eq!(migrated.marks.len(), 2);
+    assert!(migrated.marks.iter().all(|mark| mark.id.is_none()));
+    assert!(matches!(
+        &migrated.marks[0].target,
+        PersistedHarpoonMarkTarget::File { path } if path.as_ref() == Path::new("notes/a.md")
+    ));
+}
+
+#[test]
+fn test_seed_file_tolerates_unknown_extra_fields() {
+    let payload = r#"{
+        "version": 1,
+        "marks": [{"id": 3, "path": "a.md", "future_field": "ignored"}],
+        "another_future_field": 42
+    }"#;
+    let persisted: PersistedHarpoonMarks = serde_json::from_str(payload).unwrap();
+    assert_eq!(persisted.version, 1);
+    assert_eq!(persisted.marks.len(), 1);
+    assert_eq!(persisted.marks[0].id, Some(3));
+}
+
+#[gpui::test]
+async fn test_set_slot_confirms_before_overwriting_when_enabled(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one", "2.txt": "two" }))
+        .await;
+
+    cx.update(|cx| {
+        settings::SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.harpoon.get_or_insert_default().confirm_overwrite = Some(true);
+            });
+        });
+    });
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    store_file_in_slot(&project, &workspace, worktree_id, "1.txt", cx).await;
+    open_file(&project, &workspace, worktree_id, "2.txt", cx).await;
+
+    cx.dispatch_action(SetSlot(0));
+    cx.run_until_parked();
+    cx.simulate_prompt_answer("Cancel");
+    cx.run_until_parked();
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        assert_eq!(
+            store.read(cx).marks(cx)[0].target,
+            HarpoonMarkTarget::File((worktree_id, rel_path("1.txt")).into()),
+            "cancelling the prompt should leave the existing mark untouched"
+        );
+    });
+
+    cx.dispatch_action(SetSlot(0));
+    cx.run_until_parked();
+    cx.simulate_prompt_answer("Replace");
+    cx.run_until_parked();
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        assert_eq!(
+            store.read(cx).marks(cx)[0].target,
+            HarpoonMarkTarget::File((worktree_id, rel_path("2.txt")).into()),
+            "confirming the prompt should replace the occupied slot"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_persisted_marks_round_trip_through_the_key_value_store(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    // An isolated in-memory database, so this test's reads and writes can't
+    // race other tests sharing the same `/root` worktree path under the
+    // process-wide fallback database.
+    cx.update(|cx| cx.set_global(db::AppDatabase::test_new()));
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one", "2.txt": "two" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let one = ProjectPath::from((worktree_id, rel_path("1.txt")));
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark(one.clone(), None, cx).unwrap();
+        });
+    });
+    cx.run_until_parked();
+
+    // Construct a fresh `HarpoonStore` directly instead of going through
+    // `get_or_create_harpoon_store`, which would just hand back the existing
+    // in-memory instance from its `GlobalHarpoonStore` cache. Going around
+    // that cache here proves the mark above actually reached the
+    // key-value store.
+    let fs = project.read_with(cx, |project, _| project.fs().clone());
+    let reloaded = cx.update(|cx| {
+        cx.new(|cx| {
+            let mut store = HarpoonStore::new(project.clone(), fs, cx);
+            store.load_persisted_marks(cx);
+            store
+        })
+    });
+    cx.update(|cx| {
+        let marks = reloaded.read(cx).marks(cx);
+        assert_eq!(marks.len(), 1, "the mark should have been persisted and reloaded");
+        assert_eq!(marks[0].target, HarpoonMarkTarget::File(one.clone()));
+    });
+}
+
+#[gpui::test]
+async fn test_persisted_marks_survive_restart_under_worktree_scope_before_active_item_changed(
+    cx: &mut TestAppContext,
+) {
+    let app_state = init_test(cx);
+    // An isolated in-memory database, so this test's reads and writes can't
+    // race other tests sharing the same `/root` worktree path under the
+    // process-wide fallback database.
+    cx.update(|cx| cx.set_global(db::AppDatabase::test_new()));
+    cx.update(|cx| {
+        settings::SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.harpoon.get_or_insert_default().scope =
+                    Some(settings::HarpoonScopeContent::Worktree);
+            });
+        });
+    });
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+
+    // Mark a terminal before any `ActiveItemChanged` has fired, so
+    // `active_worktree` is still `None` and `scope_key` falls back to
+    // `HarpoonScopeKey::Global` even though scope is `Worktree`.
+    let cwd: Arc<Path> = path!("/root").into();
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            assert!(store.mark_terminal(cwd.clone(), cx));
+            assert_eq!(
+                store.marks(cx).len(),
+                1,
+                "the mark should be visible through the Global fallback bucket"
+            );
+        });
+    });
+    cx.run_until_parked();
+
+    // Construct a fresh `HarpoonStore` to simulate a restart, bypassing the
+    // `GlobalHarpoonStore` cache that `get_or_create_harpoon_store` would
+    // otherwise hand back.
+    let fs = project.read_with(cx, |project, _| project.fs().clone());
+    let reloaded = cx.update(|cx| {
+        cx.new(|cx| {
+            let mut store = HarpoonStore::new(project.clone(), fs, cx);
+            store.load_persisted_marks(cx);
+            store
+        })
+    });
+    cx.update(|cx| {
+        let marks = reloaded.read(cx).marks(cx);
+        assert_eq!(
+            marks.len(),
+            1,
+            "the mark made before the first active worktree should survive a restart"
+        );
+        assert_eq!(marks[0].target, HarpoonMarkTarget::Terminal(cwd));
+    });
+}
+
+#[gpui::test]
+async fn test_bounce_on_repeat_switches_to_previous_file(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one", "2.txt": "two" }))
+        .await;
+
+    cx.update(|cx| {
+        settings::SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.harpoon.get_or_insert_default().bounce_on_repeat = Some(true);
+            });
+        });
+    });
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    open_file(&project, &workspace, worktree_id, "1.txt", cx).await;
+    store_file_in_slot(&project, &workspace, worktree_id, "2.txt", cx).await;
+
+    // Already on slot 0's file: jumping to it should bounce to the
+    // previously active file instead of doing nothing.
+    cx.dispatch_action(JumpToSlot(0));
+    cx.run_until_parked();
+    workspace.update(cx, |workspace, cx| {
+        assert_eq!(
+            workspace.active_item(cx).and_then(|item| item.project_path(cx)),
+            Some((worktree_id, rel_path("1.txt")).into()),
+            "bounce_on_repeat should switch back to the previously active file"
+        );
+    });
+
+    // Jumping again now lands on slot 0's file normally, since it's no
+    // longer the active one.
+    cx.dispatch_action(JumpToSlot(0));
+    cx.run_until_parked();
+    workspace.update(cx, |workspace, cx| {
+        assert_eq!(
+            workspace.active_item(cx).and_then(|item| item.project_path(cx)),
+            Some((worktree_id, rel_path("2.txt")).into())
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_normalize_slots_sorts_by_path_and_is_undoable(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({ "1.txt": "one", "2.txt": "two", "3.txt": "three" }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("3.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("2.txt")).into(), None, cx).unwrap();
+        });
+
+        store.update(cx, |store, cx| {
+            store.normalize_slots(cx);
+        });
+        let marks = store.read(cx).marks(cx);
+        assert_eq!(
+            marks.iter().map(|mark| mark.target.clone()).collect::<Vec<_>>(),
+            vec![
+                HarpoonMarkTarget::File((worktree_id, rel_path("1.txt")).into()),
+                HarpoonMarkTarget::File((worktree_id, rel_path("2.txt")).into()),
+                HarpoonMarkTarget::File((worktree_id, rel_path("3.txt")).into()),
+            ],
+            "normalize_slots should sort occupied marks by path and compact into slots 1..N"
+        );
+
+        store.update(cx, |store, cx| {
+            assert!(store.undo_reorder(cx), "the normalize should be undoable");
+        });
+        let marks = store.read(cx).marks(cx);
+        assert_eq!(
+            marks.iter().map(|mark| mark.target.clone()).collect::<Vec<_>>(),
+            vec![
+                HarpoonMarkTarget::File((worktree_id, rel_path("3.txt")).into()),
+                HarpoonMarkTarget::File((worktree_id, rel_path("1.txt")).into()),
+                HarpoonMarkTarget::File((worktree_id, rel_path("2.txt")).into()),
+            ],
+            "undo_reorder should restore the order marks were in before the normalize"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_normalize_slots_persists_the_new_order_across_a_restart(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    // An isolated in-memory database, so this test's reads and writes can't
+    // race other tests sharing the same `/root` worktree path under the
+    // process-wide fallback database.
+    cx.update(|cx| cx.set_global(db::AppDatabase::test_new()));
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(path!("/root"), json!({ "1.txt": "one", "2.txt": "two" }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("2.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+        });
+        store.update(cx, |store, cx| {
+            store.normalize_slots(cx);
+        });
+    });
+    cx.run_until_parked();
+
+    // Construct a fresh `HarpoonStore` directly instead of going through
+    // `get_or_create_harpoon_store`, which would just hand back the existing
+    // in-memory instance from its `GlobalHarpoonStore` cache.
+    let fs = project.read_with(cx, |project, _| project.fs().clone());
+    let reloaded = cx.update(|cx| {
+        cx.new(|cx| {
+            let mut store = HarpoonStore::new(project.clone(), fs, cx);
+            store.load_persisted_marks(cx);
+            store
+        })
+    });
+    cx.update(|cx| {
+        let marks = reloaded.read(cx).marks(cx);
+        assert_eq!(
+            marks.iter().map(|mark| mark.target.clone()).collect::<Vec<_>>(),
+            vec![
+                HarpoonMarkTarget::File((worktree_id, rel_path("1.txt")).into()),
+                HarpoonMarkTarget::File((worktree_id, rel_path("2.txt")).into()),
+            ],
+            "the normalized order should survive a restart"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_active_mark_slot_tracks_the_active_item(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({ "1.txt": "one", "2.txt": "two", "3.txt": "three" }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (multi_workspace, cx) =
+        cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("2.txt")).into(), None, cx).unwrap();
+        });
+    });
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        let events = events.clone();
+        cx.subscribe(&store, move |_store, event: &ActiveMarkChanged, _cx| {
+            events.borrow_mut().push(event.0);
+        })
+        .detach();
+    });
+
+    open_file(&project, &workspace, worktree_id, "2.txt", cx).await;
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        assert_eq!(store.read(cx).active_mark_slot(), Some(1));
+    });
+
+    open_file(&project, &workspace, worktree_id, "3.txt", cx).await;
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        assert_eq!(
+            store.read(cx).active_mark_slot(),
+            None,
+            "an unmarked active file should clear the active mark slot"
+        );
+    });
+
+    assert_eq!(
+        *events.borrow(),
+        vec![Some(1), None],
+        "ActiveMarkChanged should fire once per distinct active-item slot change"
+    );
+}
+
+#[gpui::test]
+async fn test_jump_back_and_forward_retrace_jump_history(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({ "1.txt": "one", "2.txt": "two", "3.txt": "three" }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(&project, cx);
+        store.update(cx, |store, cx| {
+            store.mark((worktree_id, rel_path("1.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("2.txt")).into(), None, cx).unwrap();
+            store.mark((worktree_id, rel_path("3.txt")).into(), None, cx).unwrap();
+        });
+        let mark_ids: Vec<_> =
+            store.read(cx).marks(cx).iter().map(|mark| mark.id).collect();
+        store.update(cx, |store, cx| {
+            for &mark_id in &mark_ids {
+                store.record_jump(mark_id, cx);
+            }
+        });
+
+        let target = |file_name: &str| {
+            HarpoonMarkTarget::File((worktree_id, rel_path(file_name)).into())
+        };
+
+        store.update(cx, |store, _| {
+            assert_eq!(store.jump_back(), Some(target("2.txt")));
+            assert_eq!(store.jump_back(), Some(target("1.txt")));
+            assert_eq!(store.jump_back(), None, "clamped at the start of the history");
+        });
+        store.update(cx, |store, _| {
+            assert_eq!(store.jump_forward(), Some(target("2.txt")));
+            assert_eq!(store.jump_forward(), Some(target("3.txt")));
+            assert_eq!(store.jump_forward(), None, "clamped at the end of the history");
+        });
+
+        // Jumping somewhere new after going back discards the stale forward trail.
+        store.update(cx, |store, _| {
+            store.jump_back();
+        });
+        store.update(cx, |store, cx| {
+            store.record_jump(mark_ids[0], cx);
+        });
+        store.update(cx, |store, _| {
+            assert_eq!(store.jump_forward(), None, "a fresh jump should clear the forward trail");
+        });
+    });
+}
+
+async fn store_file_in_slot(
+    project: &Entity<Project>,
+    workspace: &Entity<Workspace>,
+    worktree_id: WorktreeId,
+    file_name: &str,
+    cx: &mut TestAppContext,
+) {
+    open_file(project, workspace, worktree_id, file_name, cx).await;
+    cx.update(|cx| {
+        let store = get_or_create_harpoon_store(project, cx);
+        store.update(cx, |store, cx| {
+            store
+                .set_slot(0, (worktree_id, rel_path(file_name)).into(), cx)
+                .unwrap();
+        });
+    });
+}
+
+async fn open_file(
+    project: &Entity<Project>,
+    workspace: &Entity<Workspace>,
+    worktree_id: WorktreeId,
+    file_name: &str,
+    cx: &mut TestAppContext,
+) {
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, rel_path(file_name)), cx)
+        })
+        .await
+        .unwrap();
+    workspace.update_in(cx, |workspace, window, cx| {
+        let editor = cx.new(|cx| Editor::for_buffer(buffer, Some(project.clone()), window, cx));
+        workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+    });
+}
+
+fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+    cx.update(|cx| {
+        let state = AppState::test(cx);
+        theme_settings::init(theme::LoadThemes::JustBase, cx);
+        crate::init(cx);
+        editor::init(cx);
+        state
+    })
+}
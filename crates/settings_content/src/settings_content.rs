@@ -280,6 +280,12 @@ pub struct SettingsContent {
     /// Settings for the which-key popup.
     pub which_key: Option<WhichKeySettingsContent>,
 
+    /// Settings for the harpoon marking workflow.
+    pub harpoon: Option<HarpoonSettingsContent>,
+
+    /// Settings for the backlinks panel.
+    pub backlinks: Option<BacklinksSettingsContent>,
+
     /// Settings related to Vim mode in Zed.
     pub vim: Option<VimSettingsContent>,
 
@@ -1139,6 +1145,278 @@ pub struct OutlinePanelSettingsContent {
     pub expand_outlines_with_depth: Option<usize>,
 }
 
+#[with_fallible_options]
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
+pub struct BacklinksSettingsContent {
+    /// The position of the backlinks panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Customize default width (in pixels) taken by the backlinks panel.
+    ///
+    /// Default: 240
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub default_width: Option<f32>,
+    /// Customize default height (in pixels) taken by the backlinks panel when
+    /// docked at the bottom.
+    ///
+    /// Default: 240
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub default_height: Option<f32>,
+    /// The maximum number of backlink entries to collect and display for a
+    /// single note.
+    ///
+    /// Default: 500
+    pub max_entries: Option<usize>,
+    /// Whether a note's links to itself (e.g. a table of contents) should be
+    /// reported as backlinks of that same note.
+    ///
+    /// Default: false
+    pub include_self_references: Option<bool>,
+    /// How strictly link targets must match a file name to be recognized.
+    ///
+    /// Default: strict
+    pub link_normalization: Option<LinkNormalizationMode>,
+    /// The maximum number of characters to show in a backlink's line-context
+    /// preview, centered on the matched link. Longer lines are truncated with
+    /// an ellipsis. `0` disables truncation.
+    ///
+    /// Default: 120
+    pub max_context_length: Option<usize>,
+    /// Whether a backlink's line-context preview preserves the line's
+    /// leading indentation, instead of trimming it. Preserving it keeps list
+    /// nesting visible, at the cost of less room for the content itself.
+    ///
+    /// Default: false
+    pub preserve_leading_indentation: Option<bool>,
+    /// The number of extra lines to show before and after a backlink's
+    /// matched line in its context preview, visually centering the match
+    /// with its neighbors dimmed. Bounded by the start/end of the source
+    /// file.
+    ///
+    /// Default: 0
+    pub context_lines: Option<usize>,
+    /// Extra regex templates for recognizing custom mention syntaxes (e.g.
+    /// `note:{name}` or `@[[{name}]]`) as backlinks, alongside standard
+    /// markdown links. `{name}` is substituted with the target note's
+    /// escaped file stem before compiling. Entries that don't compile to a
+    /// valid regex are logged and skipped.
+    ///
+    /// Default: []
+    pub custom_link_patterns: Option<Vec<String>>,
+    /// Whether the backlinks panel should open on startup.
+    ///
+    /// Default: false
+    pub starts_open: Option<bool>,
+    /// Where the backlinks panel's icon ranks among other panel icons in the
+    /// status bar, relative to the other panels' own `activation_priority`.
+    /// Lower numbers sort first.
+    ///
+    /// Default: 10
+    pub activation_priority: Option<u32>,
+    /// How backlink entries are ordered within the panel.
+    ///
+    /// Default: location
+    pub sort_order: Option<BacklinksSortOrder>,
+    /// How much vertical space each backlink entry takes up in the panel.
+    ///
+    /// Default: comfortable
+    pub density: Option<BacklinksDensity>,
+    /// How to resolve a link whose target stem matches more than one note in
+    /// the project (e.g. `Notes/Index.md` and `Archive/Index.md` both
+    /// matching a link to "Index").
+    ///
+    /// Default: heuristic
+    pub ambiguous_stem_matching: Option<AmbiguousStemPolicy>,
+    /// Whether to collect and show each backlink's line-context preview.
+    /// Disabling this skips building and truncating that text entirely,
+    /// which speeds up scans of very large vaults; the panel still shows
+    /// each entry's source path and line number, and opening an entry still
+    /// jumps to the right line.
+    ///
+    /// Default: true
+    pub show_context: Option<bool>,
+    /// Whether opening a backlink entry whose source file Zed can't render
+    /// in an editor (e.g. a non-text attachment) falls back to opening it
+    /// with the OS's default application, instead of opening a blank editor.
+    ///
+    /// Default: true
+    pub open_external_for_unsupported_files: Option<bool>,
+    /// What clicking a backlink entry does, absent any modifier key. This
+    /// doesn't affect the wiki-link hover summary, which appears without a
+    /// click and is shown regardless of this setting.
+    ///
+    /// Default: open
+    pub on_click: Option<BacklinkClickBehavior>,
+    /// Whether this worktree is excluded from backlink scans. Set this in a
+    /// worktree's local `.zed/settings.json` (e.g. a "code" root in a
+    /// multi-root workspace that also has a "vault" root) to keep its files
+    /// out of `find_backlinks`, both as scan candidates and as link targets.
+    ///
+    /// Default: false
+    pub exclude_from_scanning: Option<bool>,
+    /// What a link target or `[[wikilink]]` name is matched against. `title`
+    /// and `id` match the `title:`/`id:` field in a note's YAML frontmatter,
+    /// falling back to its filename for notes that don't have one.
+    ///
+    /// Default: filename
+    pub note_identity: Option<NoteIdentity>,
+    /// Whether the panel shows a minimal "Backlinks apply to notes" hint
+    /// instead of its normal tabs and entry list when the active item isn't
+    /// a markdown file, rather than running a scan for it.
+    ///
+    /// Default: false
+    pub collapse_for_non_notes: Option<bool>,
+    /// Whether to only show backlinks found on open (`- [ ]`) task list
+    /// items, hiding both prose mentions and completed (`- [x]`) tasks.
+    ///
+    /// Default: false
+    pub open_tasks_only: Option<bool>,
+    /// Which files are scanned as candidate backlink sources, relative to
+    /// the active note's folder. Restricting this to a folder dramatically
+    /// cuts scan cost and noise in a large vault organized into topic
+    /// folders.
+    ///
+    /// Default: vault
+    pub scan_scope: Option<BacklinkScanScope>,
+    /// The link syntax used when generating a new link to a note, e.g. for
+    /// the backlinks panel's "Replace reference" quick action.
+    ///
+    /// Default: wiki
+    pub link_syntax: Option<LinkSyntax>,
+    /// How much of the target note's path a generated link includes.
+    ///
+    /// Default: shortest
+    pub link_path_format: Option<LinkPathFormat>,
+    /// Whether a generated link includes the target note's file extension
+    /// (e.g. `Note.md` instead of `Note`).
+    ///
+    /// Default: false
+    pub link_include_extension: Option<bool>,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BacklinksSortOrder {
+    /// Sorted by target, then source path, then line number.
+    Location,
+    /// Sorted by relevance to the target note: sources in the same folder
+    /// first, then by how closely a source's name matches the target's.
+    Relevance,
+    /// Sorted by how often each source has been opened from the panel,
+    /// most-opened first, so frequently-visited notes float to the top.
+    Frequency,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BacklinksDensity {
+    /// Each backlink entry is rendered across two lines: the source file
+    /// name, then its line-context preview.
+    Comfortable,
+    /// Each backlink entry is rendered on a single line as `display_name:
+    /// context`, with tighter spacing, to fit more entries on screen.
+    Compact,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkNormalizationMode {
+    /// Link targets must match a file name exactly (aside from the `.md`
+    /// extension and URL-encoding).
+    Strict,
+    /// Whitespace, hyphens, underscores, and case are normalized on both
+    /// sides before comparison, so `my-note` and `My Note` both match
+    /// `My Note.md`.
+    Normalized,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AmbiguousStemPolicy {
+    /// When a link's target stem matches several notes, prefer the one an
+    /// explicit path fragment in the link points to, then the one in the
+    /// same folder as the source note. A match that still can't be narrowed
+    /// down this way is kept, tagged as ambiguous, rather than dropped.
+    Heuristic,
+    /// Every note sharing a link's target stem counts as a match, as if the
+    /// link were unambiguous. No entry is ever tagged as ambiguous.
+    MatchAll,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteIdentity {
+    /// Match against the note's filename, without its extension.
+    Filename,
+    /// Match against the `title:` field in the note's YAML frontmatter,
+    /// falling back to its filename when the field is absent.
+    Title,
+    /// Match against the `id:` field in the note's YAML frontmatter,
+    /// falling back to its filename when the field is absent.
+    Id,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -1158,6 +1436,104 @@ pub enum DockSide {
     Right,
 }
 
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BacklinkClickBehavior {
+    /// Opens the entry's source in the active pane, reusing an existing tab
+    /// for it if one is already open.
+    Open,
+    /// Opens the entry's source as a preview tab, the same as single-clicking
+    /// a file in the project panel: it's reused by the next preview open
+    /// instead of staying pinned.
+    Preview,
+    /// Opens the entry's source in a new split to the right of the active
+    /// pane.
+    Split,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BacklinkScanScope {
+    /// Scan every markdown file in the project.
+    Vault,
+    /// Scan only markdown files in the active note's own folder, not its
+    /// subfolders.
+    Folder,
+    /// Scan markdown files in the active note's folder and all of its
+    /// subfolders.
+    FolderRecursive,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkSyntax {
+    /// `[[Note]]`, or `[[Note|display text]]` with a display label.
+    Wiki,
+    /// `[display text](Note.md)`.
+    Markdown,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkPathFormat {
+    /// Just the target note's file stem, e.g. `Note`, with no folder path.
+    Shortest,
+    /// The target note's path relative to the source note's folder, e.g.
+    /// `../Archive/Note`.
+    Relative,
+    /// The target note's full path from the vault root, e.g.
+    /// `Archive/Note`.
+    Absolute,
+}
+
 #[derive(
     Copy,
     Clone,
@@ -1369,6 +1745,229 @@ pub struct WhichKeySettingsContent {
     pub delay_ms: Option<u64>,
 }
 
+/// Settings for the harpoon marking workflow.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct HarpoonSettingsContent {
+    /// Whether to seed a project's marks from a `.harpoon.json` file committed
+    /// at the worktree root, when no marks have been saved for it yet.
+    ///
+    /// Default: true
+    pub seed_from_project_file: Option<bool>,
+    /// How long, in milliseconds, a second `Mark` press within the window
+    /// after the first is treated as a double-tap that opens the picker
+    /// instead of marking again.
+    ///
+    /// Default: 300
+    pub double_tap_window_ms: Option<u64>,
+    /// The maximum number of marks a project can hold at once. New marks
+    /// beyond this limit are not added.
+    ///
+    /// Default: 9
+    pub max_slots: Option<usize>,
+    /// The width of the harpoon picker, in rems.
+    ///
+    /// Default: 34
+    pub picker_width: Option<f32>,
+    /// The maximum number of marks visible in the harpoon picker before it
+    /// scrolls.
+    ///
+    /// Default: 12
+    pub picker_max_rows: Option<usize>,
+    /// How long, in milliseconds, a `ClearAllMarks` can be undone via
+    /// `RestoreMarks` before the cleared marks are discarded for good.
+    ///
+    /// Default: 10000
+    pub clear_undo_window_ms: Option<u64>,
+    /// Whether the harpoon picker shows every slot up to `max_slots`,
+    /// rendering unoccupied ones as placeholder rows, instead of only the
+    /// occupied marks.
+    ///
+    /// Default: false
+    pub show_empty_slots: Option<bool>,
+    /// Whether to reopen each current mark as a background tab once a
+    /// project's marks have finished loading, so a project opens back up
+    /// anchored on its curated marks instead of raw recency.
+    ///
+    /// Default: false
+    pub restore_marks_as_tabs: Option<bool>,
+    /// The order in which marks are listed in the harpoon picker. Slot
+    /// numbers shown in the picker always reflect the true slot regardless of
+    /// this setting.
+    ///
+    /// Default: slot
+    pub picker_sort: Option<HarpoonPickerSortContent>,
+    /// Whether jumping to a mark briefly flashes the line the cursor lands
+    /// on, mirroring jump-to-definition. The view is centered on that line
+    /// regardless of this setting, unless `restore_scroll_position` restores
+    /// the mark's recorded scroll position instead.
+    ///
+    /// Default: true
+    pub flash_on_jump: Option<bool>,
+    /// Whether `harpoon::OpenAll` opens each mark after the first in its own
+    /// split, instead of as a background tab.
+    ///
+    /// Default: false
+    pub open_all_in_splits: Option<bool>,
+    /// Automatically marks the first N distinct files opened in a project
+    /// session, up to `max_slots`, so a project accumulates a useful working
+    /// set without any `Mark` presses. `0` disables this entirely.
+    ///
+    /// Default: 0
+    pub auto_mark_first: Option<usize>,
+    /// Whether a bookmark icon is shown after the title of a tab whose file
+    /// is currently marked, so the marked state is visible at a glance while
+    /// editing.
+    ///
+    /// Default: true
+    pub show_marked_indicator: Option<bool>,
+    /// Whether `harpoon::SetSlot` prompts for confirmation before replacing a
+    /// slot that already holds a mark, to protect deliberate slot layouts
+    /// from accidental overwrites.
+    ///
+    /// Default: false
+    pub confirm_overwrite: Option<bool>,
+    /// The position of the harpoon panel, the dockable alternative to the
+    /// transient `harpoon::ToggleHarpoonPicker` modal.
+    ///
+    /// Default: right
+    pub panel_dock: Option<DockPosition>,
+    /// Customize default width (in pixels) taken by the harpoon panel.
+    ///
+    /// Default: 240
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub panel_default_width: Option<f32>,
+    /// Customize default height (in pixels) taken by the harpoon panel when
+    /// docked at the bottom.
+    ///
+    /// Default: 240
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub panel_default_height: Option<f32>,
+    /// Whether the harpoon panel should open on startup.
+    ///
+    /// Default: false
+    pub panel_starts_open: Option<bool>,
+    /// Where the harpoon panel's icon ranks among other panel icons in the
+    /// status bar, relative to the other panels' own `activation_priority`.
+    /// Lower numbers sort first.
+    ///
+    /// Default: 11
+    pub panel_activation_priority: Option<u32>,
+    /// Whether marks are shared across the whole project or kept separate per
+    /// worktree, switching automatically based on the active file's
+    /// worktree. In a single-root project there's only ever one worktree, so
+    /// this has no visible effect there.
+    ///
+    /// Default: project
+    pub scope: Option<HarpoonScopeContent>,
+    /// Whether `harpoon::JumpToSlot` on the slot that's already the active
+    /// file instead switches to the previously active file, so a single jump
+    /// binding doubles as a toggle between two files.
+    ///
+    /// Default: false
+    pub bounce_on_repeat: Option<bool>,
+    /// Whether jumping to a mark restores the scroll position it was
+    /// captured at, instead of centering the view on the restored cursor.
+    /// Falls back to centering when the mark's recorded position no longer
+    /// resolves in the current buffer. A recorded row past the end of a file
+    /// that has since shrunk is clamped to its last line rather than dropped.
+    ///
+    /// Default: false
+    pub restore_scroll_position: Option<bool>,
+    /// Whether the harpoon picker shows each mark's jump count for the
+    /// current session, so marks that aren't actually getting used can be
+    /// spotted and pruned. Purely local: the count lives only in memory and
+    /// is never persisted or reported anywhere.
+    ///
+    /// Default: false
+    pub show_jump_counts: Option<bool>,
+    /// Whether marking a file prompts for a short one-line note, stored as
+    /// the new mark's comment, turning it into a lightweight TODO anchor.
+    /// Off by default so the fast path stays fast.
+    ///
+    /// Default: false
+    pub prompt_on_mark: Option<bool>,
+    /// How `harpoon::NormalizeSlots` reorders and compacts occupied marks
+    /// into slots `1..N`.
+    ///
+    /// Default: path
+    pub normalize_slots_order: Option<HarpoonNormalizeSlotsOrderContent>,
+}
+
+/// Whether marks are shared project-wide or scoped to the active worktree.
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum HarpoonScopeContent {
+    /// One shared mark set for the whole project.
+    #[default]
+    Project,
+    /// A separate mark set per worktree, following the active file.
+    Worktree,
+}
+
+/// The order in which the harpoon picker lists marks.
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum HarpoonPickerSortContent {
+    /// List marks in slot order.
+    #[default]
+    Slot,
+    /// List the most recently jumped-to mark first.
+    Recent,
+    /// List marks alphabetically by display path.
+    Alpha,
+}
+
+/// The order `harpoon::NormalizeSlots` reorders occupied marks into before
+/// compacting them into slots `1..N`.
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum HarpoonNormalizeSlotsOrderContent {
+    /// Sort marks alphabetically by display path.
+    #[default]
+    Path,
+    /// Sort marks by the order they were originally created in.
+    MarkOrder,
+}
+
 // An ExtendingVec in the settings can only accumulate new values.
 //
 // This is useful for things like private files where you only want
@@ -172,6 +172,11 @@ use crate::{
 
 pub const SERIALIZATION_THROTTLE_TIME: Duration = Duration::from_millis(200);
 
+/// How long a panel's size/dock position must stay unchanged before the new
+/// state is written to the database, so rapid drag-resizing coalesces into a
+/// single write once the drag settles.
+const PANEL_SIZE_STATE_PERSIST_DEBOUNCE: Duration = Duration::from_millis(200);
+
 static ZED_WINDOW_SIZE: LazyLock<Option<Size<Pixels>>> = LazyLock::new(|| {
     env::var("ZED_WINDOW_SIZE")
         .ok()
@@ -1411,6 +1416,7 @@ pub struct Workspace {
     _schedule_serialize_workspace: Option<Task<()>>,
     _serialize_workspace_task: Option<Task<()>>,
     _schedule_serialize_ssh_paths: Option<Task<()>>,
+    pending_panel_size_state_persist: HashMap<String, Task<Option<()>>>,
     pane_history_timestamp: Arc<AtomicUsize>,
     bounds: Bounds<Pixels>,
     pub centered_layout: bool,
@@ -1866,6 +1872,7 @@ impl Workspace {
             _schedule_serialize_workspace: None,
             _serialize_workspace_task: None,
             _schedule_serialize_ssh_paths: None,
+            pending_panel_size_state_persist: HashMap::default(),
             leader_updates_tx,
             _subscriptions: subscriptions,
             pane_history_timestamp,
@@ -2375,10 +2382,10 @@ impl Workspace {
     }
 
     pub fn persist_panel_size_state(
-        &self,
+        &mut self,
         panel_key: &str,
         size_state: dock::PanelSizeState,
-        cx: &mut App,
+        cx: &mut Context<Self>,
     ) {
         let Some(workspace_id) = self
             .database_id()
@@ -2390,16 +2397,30 @@ impl Workspace {
 
         let kvp = db::kvp::KeyValueStore::global(cx);
         let panel_key = panel_key.to_string();
-        cx.background_spawn(async move {
-            let scope = kvp.scoped(dock::PANEL_SIZE_STATE_KEY);
-            scope
-                .write(
-                    format!("{workspace_id}:{panel_key}"),
-                    serde_json::to_string(&size_state)?,
-                )
-                .await
-        })
-        .detach_and_log_err(cx);
+        // Replacing the previous entry drops (and so cancels) its pending
+        // write, so a flurry of resize ticks during a drag coalesces into the
+        // single write scheduled by the last tick once the drag settles.
+        self.pending_panel_size_state_persist.insert(
+            panel_key.clone(),
+            cx.spawn(async move |this, cx| {
+                cx.background_executor()
+                    .timer(PANEL_SIZE_STATE_PERSIST_DEBOUNCE)
+                    .await;
+                let scope = kvp.scoped(dock::PANEL_SIZE_STATE_KEY);
+                scope
+                    .write(
+                        format!("{workspace_id}:{panel_key}"),
+                        serde_json::to_string(&size_state)?,
+                    )
+                    .await?;
+                this.update(cx, |this, _| {
+                    this.pending_panel_size_state_persist.remove(&panel_key);
+                })
+                .ok();
+                anyhow::Ok(())
+            }
+            .log_err()),
+        );
     }
 
     pub fn set_panel_size_state<T: Panel>(
@@ -785,6 +785,8 @@ fn main() {
         json_schema_store::init(cx);
         miniprofiler_ui::init(*STARTUP_TIME.get().unwrap(), cx);
         which_key::init(cx);
+        harpoon::init(cx);
+        backlinks::init(cx);
         #[cfg(target_os = "windows")]
         etw_tracing::init(cx);
 